@@ -1,35 +1,186 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::program::invoke;
+use anchor_lang::solana_program::keccak;
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
 use anchor_lang::solana_program::system_instruction;
+use anchor_lang::Discriminator;
+use anchor_spl::metadata::{
+    self, mpl_token_metadata::types::DataV2, CreateMetadataAccountsV3, Metadata,
+    UpdateMetadataAccountsV2,
+};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::Token;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{metadata_pointer, transfer_fee, BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+};
+use anchor_spl::token_interface::{
+    self, Burn, CloseAccount, InitializeMint2, Mint, MintTo, Token2022, TokenAccount as SplTokenAccount,
+    TokenInterface, TransferChecked,
+};
+use spl_token_metadata_interface::state::{Field, TokenMetadata};
 
 declare_id!("Wyb111111111111111111111111111111111111111");
 
+// Decimals of mints created before decimals were configurable
+pub const TOKEN_DECIMALS: u8 = 9;
+
+// Maximum configurable decimals, which keeps the scaled virtual token reserve within u64
+pub const MAX_TOKEN_DECIMALS: u8 = 9;
+
+// Layout version written to TokenAccount and TokenHolder
+pub const CURRENT_ACCOUNT_VERSION: u8 = 2;
+
+// URI schemes accepted for token metadata
+pub const ALLOWED_METADATA_URI_SCHEMES: [&str; 3] = ["https://", "ipfs://", "ar://"];
+
+// Maximum extra tax charged on buys inside the launch window (50%)
+pub const MAX_SNIPER_TAX_BPS: u64 = 5000;
+
+// Maximum per-holder cooldown between trades (1 day)
+pub const MAX_TRADE_COOLDOWN_SECONDS: i64 = 86_400;
+
+// Default virtual reserves of the constant-product bonding curve (30 SOL against 1.073B whole tokens,
+// scaled to base units by the token's decimals)
+pub const DEFAULT_VIRTUAL_SOL_RESERVE: u64 = 30_000_000_000;
+pub const DEFAULT_VIRTUAL_TOKEN_RESERVE: u64 = 1_073_000_000;
+
+// Default real SOL reserve at which the curve graduates (85 SOL)
+pub const DEFAULT_GRADUATION_RESERVE_TARGET: u64 = 85_000_000_000;
+
+// Fixed price of whole tokens minted after the curve graduates (in lamports)
+pub const GRADUATED_PRICE_PER_TOKEN: u64 = 1_000_000;
+
+// Minimum time between two buyback-and-burn cranks of the same token (1 hour)
+pub const BUYBACK_INTERVAL_SECONDS: i64 = 3_600;
+
+// Trailing window of daily volume buckets that platform fee tiers are keyed on (30 days)
+pub const FEE_TIER_WINDOW_DAYS: usize = 30;
+pub const SECONDS_PER_DAY: i64 = 86_400;
+
+// Maximum number of platform fee tiers
+pub const MAX_FEE_TIERS: usize = 8;
+
+// Maximum number of wallets sharing a token's creator fees
+pub const MAX_FEE_SPLIT_RECIPIENTS: usize = 10;
+
+// Delay before a change to an active creator fee split takes effect (3 days)
+pub const FEE_SPLIT_TIMELOCK_SECONDS: i64 = 259_200;
+
+// Maximum share of a token's supply its vesting schedules can escrow at once (20%)
+pub const MAX_VESTING_BPS: u64 = 2000;
+
 #[program]
 pub mod wybe_token_program {
     use super::*;
 
+    #[allow(clippy::too_many_arguments)]
     pub fn initialize(
         ctx: Context<Initialize>,
         name: String,
         symbol: String,
+        metadata_uri: String,
+        metadata_hash: [u8; 32],
         creator_fee: u64,
         platform_fee: u64,
+        decimals: u8,
+        max_supply: u64,
     ) -> Result<()> {
         let token_account = &mut ctx.accounts.token_account;
         let authority = &ctx.accounts.authority;
         let treasury = &ctx.accounts.treasury;
 
         // Input validation
-        require!(!name.is_empty(), ErrorCode::InvalidTokenName);
-        require!(!symbol.is_empty(), ErrorCode::InvalidTokenSymbol);
-        require!(symbol.len() <= 8, ErrorCode::TokenSymbolTooLong);
-        require!(name.len() <= 32, ErrorCode::TokenNameTooLong);
+        validate_token_params(&name, &symbol, &metadata_uri, creator_fee, platform_fee)?;
+        require!(metadata_hash != [0u8; 32], ErrorCode::InvalidMetadataHash);
+        validate_supply_params(decimals, max_supply)?;
 
-        // Validate fees
-        require!(
-            creator_fee + platform_fee <= 1000, // Maximum 10% total fees (1000 basis points)
-            ErrorCode::InvalidFees
-        );
+        // Initialize the token account
+        token_account.name = name;
+        token_account.symbol = symbol;
+        token_account.creator_fee = creator_fee;
+        token_account.platform_fee = platform_fee;
+        token_account.authority = authority.key();
+        token_account.treasury = treasury.key();
+        token_account.metadata_uri = metadata_uri;
+        token_account.metadata_hash = metadata_hash;
+        token_account.mint = ctx.accounts.mint.key();
+        token_account.mint_authority_bump = ctx.bumps.mint_authority;
+        token_account.token_program = ctx.accounts.token_program.key();
+        token_account.decimals = decimals;
+        token_account.max_supply = max_supply;
+        token_account.init_launch_state(&Clock::get()?);
+
+        // Create the Metaplex metadata account so wallets and explorers can read it
+        let token_account_key = token_account.key();
+        let mint_authority_seeds: &[&[u8]] = &[
+            b"mint_authority",
+            token_account_key.as_ref(),
+            &[token_account.mint_authority_bump],
+        ];
+        metadata::create_metadata_accounts_v3(
+            CpiContext::new_with_signer(
+                ctx.accounts.metadata_program.to_account_info(),
+                CreateMetadataAccountsV3 {
+                    metadata: ctx.accounts.metadata.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    mint_authority: ctx.accounts.mint_authority.to_account_info(),
+                    payer: authority.to_account_info(),
+                    update_authority: ctx.accounts.mint_authority.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    rent: ctx.accounts.rent.to_account_info(),
+                },
+                &[mint_authority_seeds],
+            ),
+            token_account.metaplex_data(),
+            true,
+            true,
+            None,
+        )?;
+
+        // Emit event for indexing
+        emit!(TokenInitialized {
+            token_account: token_account.key(),
+            mint: token_account.mint,
+            name: token_account.name.clone(),
+            symbol: token_account.symbol.clone(),
+            creator_fee,
+            platform_fee,
+            authority: authority.key(),
+            treasury: treasury.key(),
+            metadata_uri: token_account.metadata_uri.clone(),
+            metadata_hash,
+            decimals,
+            max_supply,
+        });
+
+        Ok(())
+    }
+
+    // Create a token backed by a Token-2022 mint with transfer fee and embedded metadata
+    #[allow(clippy::too_many_arguments)]
+    pub fn initialize_token_2022(
+        ctx: Context<InitializeToken2022>,
+        name: String,
+        symbol: String,
+        metadata_uri: String,
+        metadata_hash: [u8; 32],
+        creator_fee: u64,
+        platform_fee: u64,
+        decimals: u8,
+        max_supply: u64,
+        max_transfer_fee: u64,
+    ) -> Result<()> {
+        let token_account = &mut ctx.accounts.token_account;
+        let authority = &ctx.accounts.authority;
+        let treasury = &ctx.accounts.treasury;
+        let mint = &ctx.accounts.mint;
+        let mint_authority = &ctx.accounts.mint_authority;
+        let token_program = &ctx.accounts.token_program;
+
+        // Input validation
+        validate_token_params(&name, &symbol, &metadata_uri, creator_fee, platform_fee)?;
+        require!(metadata_hash != [0u8; 32], ErrorCode::InvalidMetadataHash);
+        validate_supply_params(decimals, max_supply)?;
 
         // Initialize the token account
         token_account.name = name;
@@ -38,26 +189,114 @@ pub mod wybe_token_program {
         token_account.platform_fee = platform_fee;
         token_account.authority = authority.key();
         token_account.treasury = treasury.key();
-        token_account.total_supply = 0;
-        token_account.is_frozen = false;
-        token_account.is_bonding_curve_active = true;
-        token_account.bonding_curve_cap = 50000; // $50k cap for bonding curve
-        token_account.market_cap = 0;
-        token_account.metadata_uri = String::new();
-        token_account.last_updated_at = 0;
-        token_account.creation_time = Clock::get()?.unix_timestamp;
-        token_account.verified = false;
+        token_account.metadata_uri = metadata_uri;
+        token_account.metadata_hash = metadata_hash;
+        token_account.mint = mint.key();
+        token_account.mint_authority_bump = ctx.bumps.mint_authority;
+        token_account.token_program = token_program.key();
+        token_account.decimals = decimals;
+        token_account.max_supply = max_supply;
+        token_account.init_launch_state(&Clock::get()?);
+
+        let token_account_key = token_account.key();
+        let mint_authority_seeds: &[&[u8]] = &[
+            b"mint_authority",
+            token_account_key.as_ref(),
+            &[token_account.mint_authority_bump],
+        ];
+
+        // Fund the mint for its fixed extensions plus the embedded metadata
+        let token_metadata = TokenMetadata {
+            update_authority: Some(mint_authority.key()).try_into()?,
+            mint: mint.key(),
+            name: token_account.name.clone(),
+            symbol: token_account.symbol.clone(),
+            uri: token_account.metadata_uri.clone(),
+            ..Default::default()
+        };
+        let mint_space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&[
+            ExtensionType::TransferFeeConfig,
+            ExtensionType::MetadataPointer,
+        ])?;
+        let lamports = Rent::get()?.minimum_balance(mint_space + token_metadata.tlv_size_of()?);
+        invoke(
+            &system_instruction::create_account(
+                &authority.key(),
+                &mint.key(),
+                lamports,
+                mint_space as u64,
+                &token_program.key(),
+            ),
+            &[authority.to_account_info(), mint.to_account_info()],
+        )?;
+
+        // Creator and platform fees are charged on every transfer of the mint
+        let transfer_fee_bps = u16::try_from(creator_fee + platform_fee)
+            .map_err(|_| ErrorCode::InvalidFees)?;
+        invoke(
+            &transfer_fee::instruction::initialize_transfer_fee_config(
+                &token_program.key(),
+                &mint.key(),
+                Some(&mint_authority.key()),
+                Some(&mint_authority.key()),
+                transfer_fee_bps,
+                max_transfer_fee,
+            )?,
+            &[mint.to_account_info()],
+        )?;
+
+        // Point the mint's metadata at itself so no Metaplex account is needed
+        invoke(
+            &metadata_pointer::instruction::initialize(
+                &token_program.key(),
+                &mint.key(),
+                Some(mint_authority.key()),
+                Some(mint.key()),
+            )?,
+            &[mint.to_account_info()],
+        )?;
+
+        token_interface::initialize_mint2(
+            CpiContext::new(
+                token_program.to_account_info(),
+                InitializeMint2 {
+                    mint: mint.to_account_info(),
+                },
+            ),
+            decimals,
+            &mint_authority.key(),
+            None,
+        )?;
+
+        invoke_signed(
+            &spl_token_metadata_interface::instruction::initialize(
+                &token_program.key(),
+                &mint.key(),
+                &mint_authority.key(),
+                &mint.key(),
+                &mint_authority.key(),
+                token_metadata.name,
+                token_metadata.symbol,
+                token_metadata.uri,
+            ),
+            &[mint.to_account_info(), mint_authority.to_account_info()],
+            &[mint_authority_seeds],
+        )?;
 
         // Emit event for indexing
         emit!(TokenInitialized {
             token_account: token_account.key(),
+            mint: token_account.mint,
             name: token_account.name.clone(),
             symbol: token_account.symbol.clone(),
             creator_fee,
             platform_fee,
             authority: authority.key(),
             treasury: treasury.key(),
-            bonding_curve_cap: token_account.bonding_curve_cap,
+            metadata_uri: token_account.metadata_uri.clone(),
+            metadata_hash,
+            decimals,
+            max_supply,
         });
 
         Ok(())
@@ -90,6 +329,43 @@ pub mod wybe_token_program {
         token_account.creator_fee = creator_fee;
         token_account.platform_fee = platform_fee;
 
+        // Token-2022 mints charge the fees on every transfer too; Token-2022 applies the new rate two epochs later
+        if token_account.token_program == spl_token_2022::ID {
+            let mint = ctx.accounts.mint.as_ref().ok_or(ErrorCode::MintAccountsRequired)?;
+            let mint_authority = ctx.accounts.mint_authority.as_ref()
+                .ok_or(ErrorCode::MintAccountsRequired)?;
+            let token_program = ctx.accounts.token_program.as_ref()
+                .ok_or(ErrorCode::MintAccountsRequired)?;
+
+            // Keep the maximum fee per transfer set at initialization
+            let maximum_fee = {
+                let mint_info = mint.to_account_info();
+                let data = mint_info.try_borrow_data()?;
+                let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+                u64::from(state.get_extension::<transfer_fee::TransferFeeConfig>()?.newer_transfer_fee.maximum_fee)
+            };
+            let transfer_fee_bps = u16::try_from(creator_fee + platform_fee)
+                .map_err(|_| ErrorCode::InvalidFees)?;
+            let token_account_key = token_account.key();
+            let mint_authority_seeds: &[&[u8]] = &[
+                b"mint_authority",
+                token_account_key.as_ref(),
+                &[token_account.mint_authority_bump],
+            ];
+            invoke_signed(
+                &transfer_fee::instruction::set_transfer_fee(
+                    &token_program.key(),
+                    &mint.key(),
+                    &mint_authority.key(),
+                    &[],
+                    transfer_fee_bps,
+                    maximum_fee,
+                )?,
+                &[mint.to_account_info(), mint_authority.to_account_info()],
+                &[mint_authority_seeds],
+            )?;
+        }
+
         // Emit event for indexing
         emit!(FeesUpdated {
             token_account: token_account.key(),
@@ -157,93 +433,572 @@ pub mod wybe_token_program {
         amount: u64,
     ) -> Result<()> {
         let token_account = &mut ctx.accounts.token_account;
-        let creator = &ctx.accounts.creator;
         let treasury = &ctx.accounts.treasury;
         let holder = &mut ctx.accounts.holder;
+        let treasury_holder = &mut ctx.accounts.treasury_holder;
+        let clock = Clock::get()?;
+        
+        // Holder records are per token and wallet, so launch window caps cannot be dodged with fresh accounts
+        holder.init_if_new(token_account.key(), ctx.accounts.buyer.key(), ctx.bumps.holder);
+        treasury_holder.init_if_new(token_account.key(), treasury.key(), ctx.bumps.treasury_holder);
         
         // Security check: verify account is not frozen
         require!(!token_account.is_frozen, ErrorCode::AccountFrozen);
         
-        // Validate creator
+        // Validate treasury: its share is credited to the treasury's own holder account
         require!(
-            token_account.authority == creator.key(),
-            ErrorCode::Unauthorized
+            token_account.treasury == treasury.key()
+                && treasury_holder.owner == treasury.key(),
+            ErrorCode::InvalidTreasury
+        );
+        require!(
+            holder.key() != treasury_holder.key(),
+            ErrorCode::InvalidTreasury
         );
         
-        // Calculate price based on bonding curve if active
-        let (price_per_token, total_price) = if token_account.is_bonding_curve_active {
-            // Simple bonding curve: price = (current_supply / 10000)^2 + 0.01
-            let base_price = ((token_account.total_supply as f64 / 10000.0).powi(2) + 0.01) * 1_000_000.0; // In lamports
-            let price_per_token = base_price as u64;
-            let total_price = amount.checked_mul(price_per_token).ok_or(ErrorCode::CalculationError)?;
+        // The public curve only opens once any presale has ended
+        require!(
+            clock.unix_timestamp >= token_account.presale_end_time,
+            ErrorCode::PresaleActive
+        );
+        
+        // KYC check: verified-only launches reject unverified buyers
+        if token_account.verified_holders_only {
+            require!(
+                holder.is_verification_active(clock.unix_timestamp),
+                ErrorCode::HolderNotVerified
+            );
+        }
+        
+        // Cooldown check: slow down repeated trades from the same holder
+        holder.check_trade_cooldown(clock.unix_timestamp, token_account.trade_cooldown_seconds)?;
+        
+        // Anti-sniper: cap buys during the first slots after launch
+        let in_launch_window = token_account.is_in_launch_window(clock.slot);
+        if in_launch_window {
+            if token_account.sniper_max_tx_buy > 0 {
+                require!(
+                    amount <= token_account.sniper_max_tx_buy,
+                    ErrorCode::SniperTxLimitExceeded
+                );
+            }
             
-            // Check if we exceed the bonding curve cap
-            let new_market_cap = token_account.market_cap.checked_add(total_price)
+            let launch_window_bought = holder.launch_window_bought.checked_add(amount)
                 .ok_or(ErrorCode::CalculationError)?;
-                
-            if new_market_cap >= token_account.bonding_curve_cap * 1_000_000 {
-                // If we hit the cap, deactivate bonding curve for future mints
-                token_account.is_bonding_curve_active = false;
+            if token_account.sniper_max_wallet_buy > 0 {
+                require!(
+                    launch_window_bought <= token_account.sniper_max_wallet_buy,
+                    ErrorCode::SniperWalletLimitExceeded
+                );
             }
-            
-            token_account.market_cap = new_market_cap;
-            (price_per_token, total_price)
+            holder.launch_window_bought = launch_window_bought;
+        }
+        
+        require!(amount > 0, ErrorCode::TradeBelowMinimum);
+        
+        // Reject buys beyond what is left of the max supply
+        require!(
+            amount <= token_account.remaining_supply(),
+            ErrorCode::MaxSupplyExceeded
+        );
+        
+        // Calculate price based on bonding curve if active (amounts are in base units)
+        let is_curve_buy = token_account.is_bonding_curve_active;
+        let total_price = if is_curve_buy {
+            // Constant-product curve: the buyer pays what moves the virtual reserves by `amount`
+            let total_price = curve_buy_cost(
+                token_account.virtual_sol_reserve,
+                token_account.virtual_token_reserve,
+                amount,
+            )?;
+            token_account.apply_curve_buy(amount, total_price)?;
+            total_price
         } else {
             // Fixed price after bonding curve ends
-            let price_per_token = 1_000_000; // 1 SOL in lamports
-            let total_price = amount.checked_mul(price_per_token).ok_or(ErrorCode::CalculationError)?;
-            
-            // Update market cap
-            token_account.market_cap = token_account.market_cap.checked_add(total_price)
-                .ok_or(ErrorCode::CalculationError)?;
-                
-            (price_per_token, total_price)
+            token_account.graduated_cost(amount)?
+        };
+        // Average price paid per whole token
+        let price_per_token = ((total_price as u128) * (token_account.token_unit() as u128)
+            / amount as u128) as u64;
+        
+        // Sniper tax is routed to the treasury on top of its regular share
+        let sniper_tax_amount = if in_launch_window {
+            amount
+                .checked_mul(token_account.sniper_tax_bps)
+                .ok_or(ErrorCode::CalculationError)?
+                .checked_div(10000)
+                .ok_or(ErrorCode::CalculationError)?
+        } else {
+            0
+        };
+        
+        // Volume tiers discount the platform fee by the buyer's trailing 30-day volume
+        let trader_stats = &mut ctx.accounts.trader_stats;
+        trader_stats.wallet = ctx.accounts.buyer.key();
+        trader_stats.bump = ctx.bumps.trader_stats;
+        let (fee_tier, platform_fee_bps) = tiered_platform_fee(
+            token_account,
+            Some(&ctx.accounts.platform_config),
+            Some(trader_stats),
+            clock.unix_timestamp,
+        )?;
+        trader_stats.record_volume(clock.unix_timestamp, total_price)?;
+        
+        // Fees are charged on top of the price so the curve reserve receives the full price
+        let creator_fee_amount = bps_of(total_price, token_account.creator_fee)?;
+        let platform_fee_amount = bps_of(total_price, platform_fee_bps)?;
+        
+        let buyer = ctx.accounts.buyer.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+        let creator_fee_vault = &mut ctx.accounts.creator_fee_vault;
+        creator_fee_vault.token_account = token_account.key();
+        creator_fee_vault.bump = ctx.bumps.creator_fee_vault;
+        deposit_fee(&buyer, creator_fee_vault, &system_program, creator_fee_amount)?;
+        creator_fee_vault.commit_buyback(bps_of(creator_fee_amount, token_account.buyback_bps)?)?;
+        
+        // A registered referrer earns its share out of the platform fee
+        let referral_fee = match ctx.accounts.referrer.as_mut() {
+            Some(referrer) => {
+                require!(referrer.wallet != buyer.key(), ErrorCode::SelfReferral);
+                let referral_fee = bps_of(platform_fee_amount, referrer.share_bps)?;
+                transfer_lamports(&buyer, &referrer.to_account_info(), &system_program, referral_fee)?;
+                referrer.record_earnings(referral_fee)?;
+                referral_fee
+            }
+            None => 0,
+        };
+        let platform_fee_vault = &mut ctx.accounts.platform_fee_vault;
+        platform_fee_vault.bump = ctx.bumps.platform_fee_vault;
+        deposit_fee(&buyer, platform_fee_vault, &system_program, platform_fee_amount - referral_fee)?;
+        
+        // Curve buys fund the real SOL reserve held by the token account; later sales go to the treasury
+        let proceeds_to = if is_curve_buy {
+            token_account.to_account_info()
+        } else {
+            treasury.to_account_info()
         };
+        transfer_lamports(&buyer, &proceeds_to, &system_program, total_price)?;
         
-        // Calculate treasury amount (1% of minted tokens)
-        let treasury_amount = amount / 100;
-        let holder_amount = amount - treasury_amount;
+        // Calculate treasury amount (1% of minted tokens plus any sniper tax)
+        let (treasury_amount, holder_amount) = split_mint_amount(amount, sniper_tax_amount)?;
         
         // Update total supply
-        token_account.total_supply = token_account.total_supply.checked_add(amount)
+        token_account.add_supply(amount)?;
+            
+        // Credit the treasury share
+        let new_treasury_balance = treasury_holder.balance.checked_add(treasury_amount)
             .ok_or(ErrorCode::CalculationError)?;
+        token_account.track_holder_balance(treasury_holder.balance, new_treasury_balance)?;
+        treasury_holder.balance = new_treasury_balance;
             
-        // Update holder balance
-        holder.balance = holder.balance.checked_add(holder_amount)
+        let new_holder_balance = holder.balance.checked_add(holder_amount)
             .ok_or(ErrorCode::CalculationError)?;
             
+        // Enforce max transaction and max wallet limits against the new supply
+        token_account.check_holding_limits(amount, new_holder_balance)?;
+            
+        // Update holder balance; only tokens paid into the curve reserve can be sold back into it
+        token_account.track_holder_balance(holder.balance, new_holder_balance)?;
+        holder.balance = new_holder_balance;
+        if is_curve_buy {
+            holder.curve_balance = holder.curve_balance.checked_add(holder_amount)
+                .ok_or(ErrorCode::CalculationError)?;
+        }
+        holder.record_trade(clock.unix_timestamp);
+        
+        // Supply invariant: every minted token is credited to exactly one balance
+        token_account.check_supply_invariant()?;
+        
+        // Mint both shares to the SPL token accounts backing the buyer's and treasury's balances
+        let token_program = ctx.accounts.token_program.to_account_info();
+        let mint = ctx.accounts.mint.to_account_info();
+        let mint_authority = ctx.accounts.mint_authority.to_account_info();
+        mint_to_holder(
+            &token_program,
+            &mint,
+            &mint_authority,
+            token_account.key(),
+            token_account.mint_authority_bump,
+            &ctx.accounts.treasury_token_account.to_account_info(),
+            treasury_amount,
+        )?;
+        mint_to_holder(
+            &token_program,
+            &mint,
+            &mint_authority,
+            token_account.key(),
+            token_account.mint_authority_bump,
+            &ctx.accounts.buyer_token_account.to_account_info(),
+            holder_amount,
+        )?;
+        
+        // Value the new supply at the current curve price
+        token_account.refresh_market_cap()?;
+        
+        // Graduate once the real reserve reaches its target or the max supply is sold out
+        if is_curve_buy && token_account.graduate_if_complete() {
+            emit!(CurveGraduated {
+                token_account: token_account.key(),
+                real_sol_reserve: token_account.real_sol_reserve,
+                tokens_sold: token_account.tokens_sold,
+                market_cap: token_account.market_cap,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+            
         // Record the mint event with pricing information
         emit!(TokensMinted {
             token_account: token_account.key(),
-            creator: creator.key(),
+            creator: token_account.authority,
             holder: holder.key(),
             amount,
             treasury_amount,
             holder_amount,
+            sniper_tax_amount,
             price_per_token,
             total_price,
+            creator_fee: creator_fee_amount,
+            platform_fee: platform_fee_amount,
+            fee_tier,
+            referrer: ctx.accounts.referrer.as_ref().map(|referrer| referrer.wallet),
+            referral_fee,
             is_bonding_curve_active: token_account.is_bonding_curve_active,
+            timestamp: clock.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+    
+    // Sell tokens back into the bonding curve for SOL from its real reserve
+    pub fn sell_tokens(
+        ctx: Context<SellTokens>,
+        amount: u64,
+        min_sol_out: u64,
+    ) -> Result<()> {
+        let token_account = &mut ctx.accounts.token_account;
+        let seller = &ctx.accounts.seller;
+        let holder = &mut ctx.accounts.holder;
+        let clock = Clock::get()?;
+        
+        // Security check: verify account is not frozen
+        require!(!token_account.is_frozen, ErrorCode::AccountFrozen);
+        
+        // Only the curve fills sells, and only until it graduates
+        require!(token_account.is_bonding_curve_active, ErrorCode::CurveNotActive);
+        require!(amount > 0, ErrorCode::TradeBelowMinimum);
+        
+        // KYC check: verified-only launches reject unverified sellers
+        if token_account.verified_holders_only {
+            require!(
+                holder.is_verification_active(clock.unix_timestamp),
+                ErrorCode::HolderNotVerified
+            );
+        }
+        
+        // Cooldown check: slow down repeated trades from the same holder
+        holder.check_trade_cooldown(clock.unix_timestamp, token_account.trade_cooldown_seconds)?;
+        
+        // Check if seller has enough tokens, and bought them from the curve
+        require!(holder.balance >= amount, ErrorCode::InsufficientFunds);
+        holder.debit_curve_balance(amount)?;
+        
+        // Price the sell on the curve and take it out of the real reserve
+        let sol_out = curve_sell_proceeds(
+            token_account.virtual_sol_reserve,
+            token_account.virtual_token_reserve,
+            amount,
+        )?;
+        token_account.apply_curve_sell(amount, sol_out)?;
+        
+        // Volume tiers discount the platform fee by the seller's trailing 30-day volume
+        let trader_stats = &mut ctx.accounts.trader_stats;
+        trader_stats.wallet = seller.key();
+        trader_stats.bump = ctx.bumps.trader_stats;
+        let (fee_tier, platform_fee_bps) = tiered_platform_fee(
+            token_account,
+            Some(&ctx.accounts.platform_config),
+            Some(trader_stats),
+            clock.unix_timestamp,
+        )?;
+        trader_stats.record_volume(clock.unix_timestamp, sol_out)?;
+        
+        // Fees are carved out of the SOL paid to the seller
+        let creator_fee_amount = bps_of(sol_out, token_account.creator_fee)?;
+        let platform_fee_amount = bps_of(sol_out, platform_fee_bps)?;
+        let seller_receives = sol_out
+            .checked_sub(creator_fee_amount)
+            .and_then(|remaining| remaining.checked_sub(platform_fee_amount))
+            .ok_or(ErrorCode::CalculationError)?;
+        require!(seller_receives >= min_sol_out, ErrorCode::SlippageExceeded);
+        
+        let reserve = token_account.to_account_info();
+        let creator_fee_vault = &mut ctx.accounts.creator_fee_vault;
+        move_lamports(&reserve, &creator_fee_vault.to_account_info(), creator_fee_amount)?;
+        creator_fee_vault.record_deposit(creator_fee_amount)?;
+        creator_fee_vault.commit_buyback(bps_of(creator_fee_amount, token_account.buyback_bps)?)?;
+        
+        // A registered referrer earns its share out of the platform fee
+        let referral_fee = match ctx.accounts.referrer.as_mut() {
+            Some(referrer) => {
+                require!(referrer.wallet != seller.key(), ErrorCode::SelfReferral);
+                let referral_fee = bps_of(platform_fee_amount, referrer.share_bps)?;
+                move_lamports(&reserve, &referrer.to_account_info(), referral_fee)?;
+                referrer.record_earnings(referral_fee)?;
+                referral_fee
+            }
+            None => 0,
+        };
+        let platform_fee_vault = &mut ctx.accounts.platform_fee_vault;
+        move_lamports(&reserve, &platform_fee_vault.to_account_info(), platform_fee_amount - referral_fee)?;
+        platform_fee_vault.record_deposit(platform_fee_amount - referral_fee)?;
+        move_lamports(&reserve, &seller.to_account_info(), seller_receives)?;
+        
+        // Remove the sold tokens from the holder and the supply
+        let new_holder_balance = holder.balance.checked_sub(amount)
+            .ok_or(ErrorCode::CalculationError)?;
+        token_account.total_supply = token_account.total_supply.checked_sub(amount)
+            .ok_or(ErrorCode::CalculationError)?;
+        token_account.track_holder_balance(holder.balance, new_holder_balance)?;
+        holder.balance = new_holder_balance;
+        holder.record_trade(clock.unix_timestamp);
+        token_account.check_supply_invariant()?;
+        burn_from_holder(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.mint.to_account_info(),
+            &ctx.accounts.seller_token_account.to_account_info(),
+            &seller.to_account_info(),
+            amount,
+        )?;
+        
+        // Value the remaining supply at the new curve price
+        token_account.refresh_market_cap()?;
+        
+        emit!(TokensSold {
+            token_account: token_account.key(),
+            seller: seller.key(),
+            holder: holder.key(),
+            amount,
+            sol_out,
+            creator_fee: creator_fee_amount,
+            platform_fee: platform_fee_amount,
+            fee_tier,
+            referrer: ctx.accounts.referrer.as_ref().map(|referrer| referrer.wallet),
+            referral_fee,
+            seller_receives,
+            real_sol_reserve: token_account.real_sol_reserve,
+            market_cap: token_account.market_cap,
+            timestamp: clock.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+    
+    // Burn tokens from the caller's balance, permanently lowering total and max supply
+    pub fn burn_tokens(ctx: Context<BurnTokens>, amount: u64) -> Result<()> {
+        let token_account = &mut ctx.accounts.token_account;
+        let holder = &mut ctx.accounts.holder;
+        let owner = &ctx.accounts.owner;
+        
+        // Security check: verify account is not frozen
+        require!(!token_account.is_frozen, ErrorCode::AccountFrozen);
+        
+        // Update holder balance and supply, then burn the same amount from the owner's token account
+        token_account.burn_holder_tokens(holder, amount)?;
+        burn_from_holder(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.mint.to_account_info(),
+            &ctx.accounts.owner_token_account.to_account_info(),
+            &owner.to_account_info(),
+            amount,
+        )?;
+        token_account.refresh_market_cap()?;
+        
+        emit!(TokensBurned {
+            token_account: token_account.key(),
+            owner: owner.key(),
+            holder: holder.key(),
+            amount,
+            unsold_allocation: false,
+            total_supply: token_account.total_supply,
+            max_supply: token_account.max_supply,
             timestamp: Clock::get()?.unix_timestamp,
         });
         
         Ok(())
     }
     
+    // Burn the allocation left unsold under the max supply once the curve has graduated
+    pub fn burn_unsold_allocation(ctx: Context<UpdateTradingRules>) -> Result<()> {
+        let token_account = &mut ctx.accounts.token_account;
+        let authority = &ctx.accounts.authority;
+        
+        // Security check: verify account is not frozen
+        require!(!token_account.is_frozen, ErrorCode::AccountFrozen);
+        
+        // Validate authority
+        require!(
+            token_account.authority == authority.key(),
+            ErrorCode::Unauthorized
+        );
+        
+        // Only the creator of a graduated, capped token can burn what the curve did not sell
+        require!(!token_account.is_bonding_curve_active, ErrorCode::NotGraduated);
+        let amount = token_account.remaining_supply();
+        require!(
+            token_account.max_supply > 0 && amount > 0,
+            ErrorCode::NoUnsoldAllocation
+        );
+        
+        token_account.max_supply = token_account.total_supply;
+        token_account.last_updated_at = Clock::get()?.unix_timestamp;
+        
+        emit!(TokensBurned {
+            token_account: token_account.key(),
+            owner: authority.key(),
+            holder: Pubkey::default(),
+            amount,
+            unsold_allocation: true,
+            total_supply: token_account.total_supply,
+            max_supply: token_account.max_supply,
+            timestamp: token_account.last_updated_at,
+        });
+        
+        Ok(())
+    }
+    
+    // Permissionless crank: spend the creator fees committed to buybacks on the curve and burn the tokens
+    pub fn execute_buyback(ctx: Context<ExecuteBuyback>) -> Result<()> {
+        let token_account = &mut ctx.accounts.token_account;
+        let creator_fee_vault = &mut ctx.accounts.creator_fee_vault;
+        let clock = Clock::get()?;
+        
+        // Security check: verify account is not frozen
+        require!(!token_account.is_frozen, ErrorCode::AccountFrozen);
+        
+        // Buybacks are filled by the curve, so they stop once it graduates
+        require!(token_account.is_bonding_curve_active, ErrorCode::CurveNotActive);
+        
+        // Rate limit: one buyback per interval
+        require!(
+            clock.unix_timestamp >= creator_fee_vault.last_buyback_at.saturating_add(BUYBACK_INTERVAL_SECONDS),
+            ErrorCode::BuybackTooSoon
+        );
+        
+        // Buy as many tokens as the committed SOL allows, within the max supply
+        let tokens_burned = curve_tokens_for(
+            token_account.virtual_sol_reserve,
+            token_account.virtual_token_reserve,
+            creator_fee_vault.buyback_reserved,
+        )?
+        .min(token_account.remaining_supply());
+        require!(tokens_burned > 0, ErrorCode::NothingToBuyBack);
+        let sol_spent = curve_buy_cost(
+            token_account.virtual_sol_reserve,
+            token_account.virtual_token_reserve,
+            tokens_burned,
+        )?;
+        
+        // Move the SOL into the curve reserve, then burn what it bought
+        move_lamports(&creator_fee_vault.to_account_info(), &token_account.to_account_info(), sol_spent)?;
+        creator_fee_vault.spend_buyback(sol_spent)?;
+        creator_fee_vault.last_buyback_at = clock.unix_timestamp;
+        token_account.apply_curve_buy(tokens_burned, sol_spent)?;
+        token_account.add_supply(tokens_burned)?;
+        token_account.burn_supply(tokens_burned)?;
+        token_account.refresh_market_cap()?;
+        
+        if token_account.graduate_if_complete() {
+            emit!(CurveGraduated {
+                token_account: token_account.key(),
+                real_sol_reserve: token_account.real_sol_reserve,
+                tokens_sold: token_account.tokens_sold,
+                market_cap: token_account.market_cap,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+        
+        emit!(BuybackExecuted {
+            token_account: token_account.key(),
+            cranker: ctx.accounts.cranker.key(),
+            sol_spent,
+            tokens_burned,
+            buyback_remaining: creator_fee_vault.buyback_reserved,
+            timestamp: clock.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+    
+    // Quote a buy spending `amount_in` lamports (fees included) without changing any state.
+    // Quotes are returned through return data so clients can read them by simulating the instruction.
+    pub fn quote_buy(ctx: Context<QuoteTrade>, amount_in: u64) -> Result<TradeQuote> {
+        let clock = Clock::get()?;
+        let accounts = &ctx.accounts;
+        let (_, platform_fee_bps) = tiered_platform_fee(
+            &accounts.token_account,
+            accounts.platform_config.as_deref(),
+            accounts.trader_stats.as_deref(),
+            clock.unix_timestamp,
+        )?;
+        accounts.token_account.quote_buy(amount_in, clock.slot, platform_fee_bps)
+    }
+    
+    // Quote selling `tokens_in` back into the curve without changing any state
+    pub fn quote_sell(ctx: Context<QuoteTrade>, tokens_in: u64) -> Result<TradeQuote> {
+        let accounts = &ctx.accounts;
+        let (_, platform_fee_bps) = tiered_platform_fee(
+            &accounts.token_account,
+            accounts.platform_config.as_deref(),
+            accounts.trader_stats.as_deref(),
+            Clock::get()?.unix_timestamp,
+        )?;
+        accounts.token_account.quote_sell(tokens_in, platform_fee_bps)
+    }
+    
+    // Quote an `execute_trade` of `amount` tokens at `price` without changing any state
+    pub fn quote_trade(ctx: Context<QuoteTrade>, amount: u64, price: u64) -> Result<TradeQuote> {
+        let accounts = &ctx.accounts;
+        let (_, platform_fee_bps) = tiered_platform_fee(
+            &accounts.token_account,
+            accounts.platform_config.as_deref(),
+            accounts.trader_stats.as_deref(),
+            Clock::get()?.unix_timestamp,
+        )?;
+        accounts.token_account.quote_trade(amount, price, platform_fee_bps)
+    }
+    
     // Execute token trade with fees
     pub fn execute_trade(
         ctx: Context<ExecuteTrade>,
         amount: u64,
         price: u64,
     ) -> Result<()> {
-        let token_account = &ctx.accounts.token_account;
+        let token_account = &mut ctx.accounts.token_account;
         let seller = &mut ctx.accounts.seller;
         let buyer = &mut ctx.accounts.buyer;
         let treasury = &ctx.accounts.treasury;
         let creator = &ctx.accounts.creator;
+        let now = Clock::get()?.unix_timestamp;
         
         // Security check: verify account is not frozen
         require!(!token_account.is_frozen, ErrorCode::AccountFrozen);
         
+        // Both holder PDAs would alias the same account
+        require!(
+            ctx.accounts.seller_wallet.key() != ctx.accounts.buyer_wallet.key(),
+            ErrorCode::SelfTrade
+        );
+        
+        // KYC check: verified-only launches reject unverified participants
+        if token_account.verified_holders_only {
+            require!(seller.is_verification_active(now), ErrorCode::HolderNotVerified);
+            require!(buyer.is_verification_active(now), ErrorCode::HolderNotVerified);
+        }
+        
+        // Cooldown check: both sides must wait out the cooldown between trades
+        seller.check_trade_cooldown(now, token_account.trade_cooldown_seconds)?;
+        buyer.check_trade_cooldown(now, token_account.trade_cooldown_seconds)?;
+        
         // Check if seller has enough tokens
         require!(seller.balance >= amount, ErrorCode::InsufficientFunds);
         
@@ -258,8 +1013,18 @@ pub mod wybe_token_program {
             .checked_div(10000)
             .ok_or(ErrorCode::CalculationError)?;
             
+        // The seller pays the fees, so the seller's volume tier applies
+        let seller_stats = &mut ctx.accounts.seller_stats;
+        seller_stats.wallet = ctx.accounts.seller_wallet.key();
+        seller_stats.bump = ctx.bumps.seller_stats;
+        let (fee_tier, platform_fee_bps) = tiered_platform_fee(
+            token_account,
+            Some(&ctx.accounts.platform_config),
+            Some(seller_stats),
+            now,
+        )?;
         let platform_fee_amount = trade_value
-            .checked_mul(token_account.platform_fee)
+            .checked_mul(platform_fee_bps)
             .ok_or(ErrorCode::CalculationError)?
             .checked_div(10000)
             .ok_or(ErrorCode::CalculationError)?;
@@ -269,13 +1034,107 @@ pub mod wybe_token_program {
             
         let seller_receives = trade_value.checked_sub(total_fees)
             .ok_or(ErrorCode::CalculationError)?;
+        
+        // The buyer pays the trade value: fees into the vaults, the rest to the seller
+        let buyer_wallet = ctx.accounts.buyer_wallet.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+        let creator_fee_vault = &mut ctx.accounts.creator_fee_vault;
+        creator_fee_vault.token_account = token_account.key();
+        creator_fee_vault.bump = ctx.bumps.creator_fee_vault;
+        deposit_fee(&buyer_wallet, creator_fee_vault, &system_program, creator_fee_amount)?;
+        creator_fee_vault.commit_buyback(bps_of(creator_fee_amount, token_account.buyback_bps)?)?;
+        
+        // A registered referrer earns its share out of the platform fee; neither side can refer itself
+        let referral_fee = match ctx.accounts.referrer.as_mut() {
+            Some(referrer) => {
+                require!(
+                    referrer.wallet != buyer_wallet.key() && referrer.wallet != ctx.accounts.seller_wallet.key(),
+                    ErrorCode::SelfReferral
+                );
+                let referral_fee = bps_of(platform_fee_amount, referrer.share_bps)?;
+                transfer_lamports(&buyer_wallet, &referrer.to_account_info(), &system_program, referral_fee)?;
+                referrer.record_earnings(referral_fee)?;
+                referral_fee
+            }
+            None => 0,
+        };
+        let platform_fee_vault = &mut ctx.accounts.platform_fee_vault;
+        platform_fee_vault.bump = ctx.bumps.platform_fee_vault;
+        deposit_fee(&buyer_wallet, platform_fee_vault, &system_program, platform_fee_amount - referral_fee)?;
+        transfer_lamports(
+            &buyer_wallet,
+            &ctx.accounts.seller_wallet.to_account_info(),
+            &system_program,
+            seller_receives,
+        )?;
             
-        // Update token balances
-        seller.balance = seller.balance.checked_sub(amount)
+        // Token-2022 withholds its transfer fee from what the buyer receives; withdraw_transfer_fees
+        // later sends it to the treasury, so the treasury's balance is credited with it now
+        let transfer_fee = if token_account.token_program == spl_token_2022::ID {
+            epoch_transfer_fee(&ctx.accounts.mint.to_account_info(), amount)?
+        } else {
+            0
+        };
+        let buyer_receives = amount.checked_sub(transfer_fee)
             .ok_or(ErrorCode::CalculationError)?;
-            
-        buyer.balance = buyer.balance.checked_add(amount)
+        let treasury_wallet = token_account.treasury;
+        
+        let mut new_buyer_balance = buyer.balance.checked_add(buyer_receives)
             .ok_or(ErrorCode::CalculationError)?;
+        let mut new_seller_balance = seller.balance.checked_sub(amount)
+            .ok_or(ErrorCode::CalculationError)?;
+        if treasury_wallet == ctx.accounts.buyer_wallet.key() {
+            new_buyer_balance = new_buyer_balance.checked_add(transfer_fee)
+                .ok_or(ErrorCode::CalculationError)?;
+        } else if treasury_wallet == ctx.accounts.seller_wallet.key() {
+            new_seller_balance = new_seller_balance.checked_add(transfer_fee)
+                .ok_or(ErrorCode::CalculationError)?;
+        } else if transfer_fee > 0 {
+            let treasury_holder = ctx.accounts.treasury_holder.as_mut()
+                .ok_or(ErrorCode::InvalidTreasury)?;
+            treasury_holder.init_if_new(token_account.key(), treasury_wallet, ctx.bumps.treasury_holder);
+            let new_treasury_balance = treasury_holder.balance.checked_add(transfer_fee)
+                .ok_or(ErrorCode::CalculationError)?;
+            token_account.track_holder_balance(treasury_holder.balance, new_treasury_balance)?;
+            treasury_holder.balance = new_treasury_balance;
+        }
+            
+        // Enforce max transaction and max wallet limits
+        token_account.check_holding_limits(amount, new_buyer_balance)?;
+            
+        // Update token balances; the seller's curve-bought tokens go to the buyer first
+        token_account.track_holder_balance(seller.balance, new_seller_balance)?;
+        token_account.track_holder_balance(buyer.balance, new_buyer_balance)?;
+        seller.balance = new_seller_balance;
+        buyer.balance = new_buyer_balance;
+        seller.transfer_curve_balance(buyer, buyer_receives)?;
+        seller.clamp_curve_balance();
+        token_account.check_supply_invariant()?;
+        
+        // Move the traded tokens between the SPL token accounts backing both balances
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.seller_token_account.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.buyer_token_account.to_account_info(),
+                    authority: ctx.accounts.seller_wallet.to_account_info(),
+                },
+            ),
+            amount,
+            ctx.accounts.mint.decimals,
+        )?;
+        
+        seller.record_trade(now);
+        buyer.record_trade(now);
+        
+        // Count the trade towards the volume of both sides
+        ctx.accounts.seller_stats.record_volume(now, trade_value)?;
+        let buyer_stats = &mut ctx.accounts.buyer_stats;
+        buyer_stats.wallet = ctx.accounts.buyer_wallet.key();
+        buyer_stats.bump = ctx.bumps.buyer_stats;
+        buyer_stats.record_volume(now, trade_value)?;
         
         // Record the trade event
         emit!(TradeExecuted {
@@ -288,9 +1147,13 @@ pub mod wybe_token_program {
             trade_value,
             creator_fee: creator_fee_amount,
             platform_fee: platform_fee_amount,
+            fee_tier,
+            referrer: ctx.accounts.referrer.as_ref().map(|referrer| referrer.wallet),
+            referral_fee,
             seller_receives,
+            transfer_fee,
             treasury: treasury.key(),
-            timestamp: Clock::get()?.unix_timestamp,
+            timestamp: now,
         });
         
         Ok(())
@@ -347,47 +1210,156 @@ pub mod wybe_token_program {
         // Check if milestone has been reached
         require!(milestone_reached, ErrorCode::MilestoneNotReached);
         
-        // In a real implementation, this would transfer the accumulated fees
-        // from a fee storage account to the creator's wallet
+        // Split creator fees are claimed by each recipient through claim_fee_split
+        require!(!token_account.fee_split_enabled, ErrorCode::FeeSplitActive);
+        
+        // Pay out everything collected in the creator fee vault since the last claim
+        let amount = withdraw_fees(&mut ctx.accounts.creator_fee_vault, &creator.to_account_info())?;
         
         emit!(CreatorFeesClaimed {
             token_account: token_account.key(),
             creator: creator.key(),
             milestone_reached,
+            amount,
             timestamp: Clock::get()?.unix_timestamp,
         });
         
         Ok(())
     }
     
-    // Record token launch on-chain
-    pub fn record_token_launch(
-        ctx: Context<RecordTokenLaunch>,
-        launch_data: String,
-    ) -> Result<()> {
-        let token_account = &ctx.accounts.token_account;
+    // Claim platform fees collected from curve buys
+    pub fn claim_platform_fees(ctx: Context<ClaimPlatformFees>) -> Result<()> {
         let authority = &ctx.accounts.authority;
-        let launch_record = &mut ctx.accounts.launch_record;
         
-        // Security check: verify account is not frozen
-        require!(!token_account.is_frozen, ErrorCode::AccountFrozen);
+        let amount = withdraw_fees(&mut ctx.accounts.platform_fee_vault, &authority.to_account_info())?;
         
-        // Validate authority
-        require!(
-            token_account.authority == authority.key(),
-            ErrorCode::Unauthorized
-        );
+        emit!(PlatformFeesClaimed {
+            platform_fee_vault: ctx.accounts.platform_fee_vault.key(),
+            authority: authority.key(),
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+    
+    // Sweep withheld Token-2022 transfer fees into the mint and withdraw them to the treasury
+    pub fn withdraw_transfer_fees<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawTransferFees<'info>>,
+    ) -> Result<()> {
+        let token_account = &ctx.accounts.token_account;
+        let mint = &ctx.accounts.mint;
+        let mint_authority = &ctx.accounts.mint_authority;
+        let token_program = &ctx.accounts.token_program;
+        let balance_before = ctx.accounts.treasury_token_account.amount;
+        
+        // Harvesting is permissionless; any holder token accounts passed have their withheld fees moved to the mint
+        if !ctx.remaining_accounts.is_empty() {
+            let sources: Vec<&Pubkey> = ctx.remaining_accounts.iter().map(|account| account.key).collect();
+            let mut accounts = vec![mint.to_account_info()];
+            accounts.extend(ctx.remaining_accounts.iter().cloned());
+            invoke(
+                &transfer_fee::instruction::harvest_withheld_tokens_to_mint(
+                    &token_program.key(),
+                    &mint.key(),
+                    &sources,
+                )?,
+                &accounts,
+            )?;
+        }
+        
+        // The mint authority PDA is the withdraw-withheld authority
+        let token_account_key = token_account.key();
+        let mint_authority_seeds: &[&[u8]] = &[
+            b"mint_authority",
+            token_account_key.as_ref(),
+            &[token_account.mint_authority_bump],
+        ];
+        invoke_signed(
+            &transfer_fee::instruction::withdraw_withheld_tokens_from_mint(
+                &token_program.key(),
+                &mint.key(),
+                &ctx.accounts.treasury_token_account.key(),
+                &mint_authority.key(),
+                &[],
+            )?,
+            &[
+                mint.to_account_info(),
+                ctx.accounts.treasury_token_account.to_account_info(),
+                mint_authority.to_account_info(),
+            ],
+            &[mint_authority_seeds],
+        )?;
+        
+        ctx.accounts.treasury_token_account.reload()?;
+        let amount = ctx.accounts.treasury_token_account.amount
+            .checked_sub(balance_before)
+            .ok_or(ErrorCode::CalculationError)?;
+        
+        emit!(TransferFeesWithdrawn {
+            token_account: token_account_key,
+            mint: mint.key(),
+            destination: ctx.accounts.treasury_token_account.key(),
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+    
+    // Record token launch on-chain
+    pub fn record_token_launch(
+        ctx: Context<RecordTokenLaunch>,
+        launch_params: LaunchParams,
+    ) -> Result<()> {
+        let token_account = &ctx.accounts.token_account;
+        let authority = &ctx.accounts.authority;
+        let launch_record = &mut ctx.accounts.launch_record;
+        
+        // Security check: verify account is not frozen
+        require!(!token_account.is_frozen, ErrorCode::AccountFrozen);
         
-        // Save launch data
+        // Validate authority
+        require!(
+            token_account.authority == authority.key(),
+            ErrorCode::Unauthorized
+        );
+        
+        // Validate launch parameters
+        require!(launch_params.initial_price > 0, ErrorCode::InvalidLaunchParams);
+        require!(
+            launch_params.dev_buy_amount <= token_account.total_supply,
+            ErrorCode::InvalidLaunchParams
+        );
+        require!(
+            launch_params.metadata_hash != [0u8; 32],
+            ErrorCode::InvalidLaunchParams
+        );
+        
+        // Save launch data from the token's current on-chain state
         launch_record.token_account = token_account.key();
         launch_record.launch_date = Clock::get()?.unix_timestamp;
-        launch_record.launch_data = launch_data;
-        launch_record.is_verified = true;
+        launch_record.launch_params = launch_params;
+        launch_record.initial_market_cap = token_account.market_cap;
+        launch_record.initial_holder_count = u16::try_from(token_account.holder_count)
+            .unwrap_or(u16::MAX);
+        launch_record.creator_wallet = token_account.authority;
+        launch_record.presale_tokens_sold = 0;
+        launch_record.presale_proceeds = 0;
+        launch_record.bump = ctx.bumps.launch_record;
+        
+        // Only a registered verifier can mark the launch as verified
+        launch_record.is_verified = false;
+        launch_record.verified_by = Pubkey::default();
         
         emit!(TokenLaunchRecorded {
             token_account: token_account.key(),
             launch_record: launch_record.key(),
             launch_date: launch_record.launch_date,
+            curve_type: launch_record.launch_params.curve_type,
+            metadata_hash: launch_record.launch_params.metadata_hash,
+            initial_market_cap: launch_record.initial_market_cap,
+            initial_holder_count: launch_record.initial_holder_count,
             authority: authority.key(),
         });
         
@@ -419,7 +1391,8 @@ pub mod wybe_token_program {
     // Update token metadata URI
     pub fn update_token_metadata(
         ctx: Context<UpdateTokenMetadata>,
-        new_uri: String
+        new_uri: String,
+        content_hash: [u8; 32],
     ) -> Result<()> {
         let token_account = &mut ctx.accounts.token_account;
         let authority = &ctx.accounts.authority;
@@ -433,17 +1406,252 @@ pub mod wybe_token_program {
             ErrorCode::Unauthorized
         );
         
-        // Validate URI
-        require!(!new_uri.is_empty(), ErrorCode::InvalidMetadataUri);
-        require!(new_uri.len() <= 200, ErrorCode::MetadataUriTooLong);
+        // Locked metadata can never change again
+        require!(!token_account.metadata_locked, ErrorCode::MetadataLocked);
+        
+        // Validate URI and the hash of the JSON it points to
+        validate_metadata_uri(&new_uri)?;
+        require!(content_hash != [0u8; 32], ErrorCode::InvalidMetadataHash);
         
         // Update metadata URI
-        token_account.metadata_uri = new_uri;
+        token_account.metadata_uri = new_uri.clone();
+        token_account.metadata_hash = content_hash;
         token_account.last_updated_at = Clock::get()?.unix_timestamp;
         
+        let token_account_key = token_account.key();
+        let mint_authority_seeds: &[&[u8]] = &[
+            b"mint_authority",
+            token_account_key.as_ref(),
+            &[token_account.mint_authority_bump],
+        ];
+        
+        if token_account.token_program == spl_token_2022::ID {
+            // Mirror the new URI into the metadata embedded in the Token-2022 mint
+            let mint = ctx.accounts.mint.to_account_info();
+            let (old_size, new_size) = {
+                let data = mint.try_borrow_data()?;
+                let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+                let mut token_metadata = state.get_variable_len_extension::<TokenMetadata>()?;
+                let old_size = token_metadata.tlv_size_of()?;
+                token_metadata.update(Field::Uri, new_uri.clone());
+                (old_size, token_metadata.tlv_size_of()?)
+            };
+            
+            // Top up rent when the longer URI makes the mint grow
+            let new_len = mint.data_len() + new_size.saturating_sub(old_size);
+            let required = Rent::get()?.minimum_balance(new_len);
+            if required > mint.lamports() {
+                invoke(
+                    &system_instruction::transfer(&authority.key(), &mint.key(), required - mint.lamports()),
+                    &[
+                        authority.to_account_info(),
+                        mint.clone(),
+                        ctx.accounts.system_program.to_account_info(),
+                    ],
+                )?;
+            }
+            
+            invoke_signed(
+                &spl_token_metadata_interface::instruction::update_field(
+                    &ctx.accounts.token_program.key(),
+                    &mint.key(),
+                    &ctx.accounts.mint_authority.key(),
+                    Field::Uri,
+                    new_uri.clone(),
+                ),
+                &[mint, ctx.accounts.mint_authority.to_account_info()],
+                &[mint_authority_seeds],
+            )?;
+        } else {
+            // Mirror the new URI into the Metaplex metadata account
+            let metadata_account = ctx.accounts.metadata.as_ref()
+                .ok_or(ErrorCode::MissingMetadataAccount)?;
+            let metadata_program = ctx.accounts.metadata_program.as_ref()
+                .ok_or(ErrorCode::MissingMetadataAccount)?;
+            metadata::update_metadata_accounts_v2(
+                CpiContext::new_with_signer(
+                    metadata_program.to_account_info(),
+                    UpdateMetadataAccountsV2 {
+                        metadata: metadata_account.to_account_info(),
+                        update_authority: ctx.accounts.mint_authority.to_account_info(),
+                    },
+                    &[mint_authority_seeds],
+                ),
+                None,
+                Some(token_account.metaplex_data()),
+                None,
+                None,
+            )?;
+        }
+        
         emit!(TokenMetadataUpdated {
             token_account: token_account.key(),
             new_uri,
+            content_hash,
+            metadata_locked: token_account.metadata_locked,
+            authority: authority.key(),
+            timestamp: token_account.last_updated_at,
+        });
+        
+        Ok(())
+    }
+    
+    // Permanently lock the token metadata against further updates
+    pub fn lock_metadata(ctx: Context<UpdateTokenMetadata>) -> Result<()> {
+        let token_account = &mut ctx.accounts.token_account;
+        let authority = &ctx.accounts.authority;
+        
+        // Security check: verify account is not frozen
+        require!(!token_account.is_frozen, ErrorCode::AccountFrozen);
+        
+        // Validate authority
+        require!(
+            token_account.authority == authority.key(),
+            ErrorCode::Unauthorized
+        );
+        
+        // Check if already locked
+        require!(!token_account.metadata_locked, ErrorCode::MetadataLocked);
+        
+        let token_account_key = token_account.key();
+        let mint_authority_seeds: &[&[u8]] = &[
+            b"mint_authority",
+            token_account_key.as_ref(),
+            &[token_account.mint_authority_bump],
+        ];
+        
+        if token_account.token_program == spl_token_2022::ID {
+            // Drop the embedded metadata update authority so nobody can change it
+            invoke_signed(
+                &spl_token_metadata_interface::instruction::update_authority(
+                    &ctx.accounts.token_program.key(),
+                    &ctx.accounts.mint.key(),
+                    &ctx.accounts.mint_authority.key(),
+                    Option::<Pubkey>::None.try_into()?,
+                ),
+                &[
+                    ctx.accounts.mint.to_account_info(),
+                    ctx.accounts.mint_authority.to_account_info(),
+                ],
+                &[mint_authority_seeds],
+            )?;
+        } else {
+            // Mark the Metaplex metadata account immutable
+            let metadata_account = ctx.accounts.metadata.as_ref()
+                .ok_or(ErrorCode::MissingMetadataAccount)?;
+            let metadata_program = ctx.accounts.metadata_program.as_ref()
+                .ok_or(ErrorCode::MissingMetadataAccount)?;
+            metadata::update_metadata_accounts_v2(
+                CpiContext::new_with_signer(
+                    metadata_program.to_account_info(),
+                    UpdateMetadataAccountsV2 {
+                        metadata: metadata_account.to_account_info(),
+                        update_authority: ctx.accounts.mint_authority.to_account_info(),
+                    },
+                    &[mint_authority_seeds],
+                ),
+                None,
+                None,
+                None,
+                Some(false),
+            )?;
+        }
+        
+        token_account.metadata_locked = true;
+        token_account.last_updated_at = Clock::get()?.unix_timestamp;
+        
+        emit!(MetadataLocked {
+            token_account: token_account.key(),
+            metadata_uri: token_account.metadata_uri.clone(),
+            authority: authority.key(),
+            timestamp: token_account.last_updated_at,
+        });
+        
+        Ok(())
+    }
+    
+    // Upgrade a v1 token account to the current layout
+    pub fn migrate_token_account(ctx: Context<MigrateTokenAccount>) -> Result<()> {
+        let account_info = ctx.accounts.token_account.to_account_info();
+        let authority = &ctx.accounts.authority;
+        
+        let v1 = deserialize_v1::<TokenAccountV1>(
+            &account_info.try_borrow_data()?,
+            &TokenAccount::DISCRIMINATOR,
+            TokenAccountV1::LEN,
+        )?;
+        
+        // Validate authority
+        require!(v1.authority == authority.key(), ErrorCode::Unauthorized);
+        
+        let migrated = TokenAccount::from_v1(v1);
+        realloc_and_write(
+            &account_info,
+            &authority.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            8 + TokenAccount::INIT_SPACE,
+            &migrated,
+        )?;
+        
+        emit!(AccountMigrated {
+            account: account_info.key(),
+            version: CURRENT_ACCOUNT_VERSION,
+            payer: authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+    
+    // Back a migrated v1 token with an SPL mint and Metaplex metadata; its holders are migrated onto it next
+    pub fn attach_mint(ctx: Context<AttachMint>) -> Result<()> {
+        let token_account = &mut ctx.accounts.token_account;
+        let authority = &ctx.accounts.authority;
+        
+        // Validate authority
+        require!(
+            token_account.authority == authority.key(),
+            ErrorCode::Unauthorized
+        );
+        
+        // Only v1 tokens were created without a mint
+        require!(token_account.mint == Pubkey::default(), ErrorCode::MintAlreadyAttached);
+        
+        token_account.mint = ctx.accounts.mint.key();
+        token_account.mint_authority_bump = ctx.bumps.mint_authority;
+        token_account.token_program = ctx.accounts.token_program.key();
+        token_account.last_updated_at = Clock::get()?.unix_timestamp;
+        
+        // Create the Metaplex metadata account so wallets and explorers can read it
+        let token_account_key = token_account.key();
+        let mint_authority_seeds: &[&[u8]] = &[
+            b"mint_authority",
+            token_account_key.as_ref(),
+            &[token_account.mint_authority_bump],
+        ];
+        metadata::create_metadata_accounts_v3(
+            CpiContext::new_with_signer(
+                ctx.accounts.metadata_program.to_account_info(),
+                CreateMetadataAccountsV3 {
+                    metadata: ctx.accounts.metadata.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    mint_authority: ctx.accounts.mint_authority.to_account_info(),
+                    payer: authority.to_account_info(),
+                    update_authority: ctx.accounts.mint_authority.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    rent: ctx.accounts.rent.to_account_info(),
+                },
+                &[mint_authority_seeds],
+            ),
+            token_account.metaplex_data(),
+            true,
+            !token_account.metadata_locked,
+            None,
+        )?;
+        
+        emit!(MintAttached {
+            token_account: token_account_key,
+            mint: token_account.mint,
             authority: authority.key(),
             timestamp: token_account.last_updated_at,
         });
@@ -451,6 +1659,63 @@ pub mod wybe_token_program {
         Ok(())
     }
     
+    // Move a v1 token holder's balance into its holder PDA, mint it to the owner and close the v1 account.
+    // v1 holders did not record their token, so the token's authority attests that the holder belongs to it.
+    pub fn migrate_token_holder(ctx: Context<MigrateTokenHolder>) -> Result<()> {
+        let token_account = &mut ctx.accounts.token_account;
+        let holder = &mut ctx.accounts.holder;
+        let legacy_holder = ctx.accounts.legacy_holder.to_account_info();
+        let authority = &ctx.accounts.authority;
+        
+        // Validate authority
+        require!(
+            token_account.authority == authority.key(),
+            ErrorCode::Unauthorized
+        );
+        
+        let v1 = deserialize_v1::<TokenHolderV1>(
+            &legacy_holder.try_borrow_data()?,
+            &TokenHolder::DISCRIMINATOR,
+            TokenHolderV1::LEN,
+        )?;
+        require!(v1.owner == ctx.accounts.owner.key(), ErrorCode::Unauthorized);
+        
+        // A wallet that already bought since the upgrade keeps its holder PDA and gains the v1 balance
+        let balance = v1.balance;
+        if holder.version == 0 {
+            holder.set_inner(TokenHolder::from_v1(
+                TokenHolderV1 { balance: 0, ..v1 },
+                token_account.key(),
+                ctx.bumps.holder,
+            ));
+        }
+        token_account.credit_legacy_balance(holder, balance)?;
+        token_account.check_supply_invariant()?;
+        mint_to_holder(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.mint.to_account_info(),
+            &ctx.accounts.mint_authority.to_account_info(),
+            token_account.key(),
+            token_account.mint_authority_bump,
+            &ctx.accounts.owner_token_account.to_account_info(),
+            balance,
+        )?;
+        
+        // Closing the v1 account means it can never be migrated twice
+        close_program_account(&legacy_holder, &authority.to_account_info())?;
+        
+        emit!(HolderMigrated {
+            token_account: token_account.key(),
+            legacy_holder: legacy_holder.key(),
+            holder: holder.key(),
+            owner: v1.owner,
+            balance,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+    
     // Transfer ownership of the token
     pub fn transfer_token_ownership(
         ctx: Context<TransferOwnership>,
@@ -484,286 +1749,4090 @@ pub mod wybe_token_program {
         
         Ok(())
     }
-}
-
-#[derive(Accounts)]
-pub struct Initialize<'info> {
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + TokenAccount::LEN
-    )]
-    pub token_account: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    /// CHECK: This is the treasury wallet
-    pub treasury: AccountInfo<'info>,
-    pub system_program: Program<'info, System>,
-}
-
-#[derive(Accounts)]
-pub struct UpdateFees<'info> {
-    #[account(mut)]
-    pub token_account: Account<'info, TokenAccount>,
-    pub authority: Signer<'info>,
-}
-
-#[derive(Accounts)]
-pub struct EmergencyAction<'info> {
-    #[account(mut)]
-    pub token_account: Account<'info, TokenAccount>,
-    pub authority: Signer<'info>,
-}
-
-#[derive(Accounts)]
-pub struct MintTokens<'info> {
-    #[account(mut)]
-    pub token_account: Account<'info, TokenAccount>,
-    pub creator: Signer<'info>,
-    /// CHECK: This is the treasury wallet
-    pub treasury: AccountInfo<'info>,
-    #[account(mut)]
-    pub holder: Account<'info, TokenHolder>,
-}
-
-#[derive(Accounts)]
-pub struct ExecuteTrade<'info> {
-    pub token_account: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub seller: Account<'info, TokenHolder>,
-    #[account(mut)]
-    pub buyer: Account<'info, TokenHolder>,
-    /// CHECK: This is the treasury wallet for fees
-    pub treasury: AccountInfo<'info>,
-    /// CHECK: This is the creator wallet for fees
-    pub creator: AccountInfo<'info>,
-}
-
-#[derive(Accounts)]
-pub struct UpdateTreasury<'info> {
-    #[account(mut)]
-    pub token_account: Account<'info, TokenAccount>,
-    pub authority: Signer<'info>,
-    /// CHECK: This is the current treasury wallet
-    pub treasury: AccountInfo<'info>,
-}
-
-#[derive(Accounts)]
-pub struct ClaimCreatorFees<'info> {
-    pub token_account: Account<'info, TokenAccount>,
-    pub creator: Signer<'info>,
-}
-
-#[derive(Accounts)]
-pub struct RecordTokenLaunch<'info> {
-    pub token_account: Account<'info, TokenAccount>,
-    pub authority: Signer<'info>,
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + LaunchRecord::LEN
-    )]
-    pub launch_record: Account<'info, LaunchRecord>,
-    pub system_program: Program<'info, System>,
-}
-
-// New account validation struct for token verification
-#[derive(Accounts)]
-pub struct VerifyTokenStats<'info> {
-    pub token_account: Account<'info, TokenAccount>,
-    /// CHECK: This is the oracle that verifies the token stats
-    pub oracle: AccountInfo<'info>,
-    pub authority: Signer<'info>,
-}
-
-// New account validation struct for updating metadata
-#[derive(Accounts)]
-pub struct UpdateTokenMetadata<'info> {
-    #[account(mut)]
-    pub token_account: Account<'info, TokenAccount>,
-    pub authority: Signer<'info>,
-}
-
-// New account validation struct for transferring ownership
-#[derive(Accounts)]
-pub struct TransferOwnership<'info> {
-    #[account(mut)]
-    pub token_account: Account<'info, TokenAccount>,
-    pub authority: Signer<'info>,
-}
-
-#[account]
-pub struct TokenAccount {
-    pub name: String,                // 32 bytes max
-    pub symbol: String,              // 8 bytes max
-    pub creator_fee: u64,            // Fee in basis points (1/100 of 1%)
-    pub platform_fee: u64,           // Fee in basis points (1/100 of 1%)
-    pub authority: Pubkey,           // 32 bytes
-    pub treasury: Pubkey,            // 32 bytes - treasury wallet address (can be hardware wallet)
-    pub total_supply: u64,           // Total supply of tokens
-    pub is_frozen: bool,             // Emergency freeze flag
-    pub is_bonding_curve_active: bool, // Whether bonding curve is active or not
-    pub bonding_curve_cap: u64,      // Bonding curve cap (in USD)
-    pub market_cap: u64,             // Current market cap (in lamports)
-    pub metadata_uri: String,        // Token metadata URI (200 bytes max)
-    pub last_updated_at: i64,        // Last time the token was updated
-    pub creation_time: i64,          // Time when token was created
-    pub verified: bool,              // Whether token is verified
-}
-
-impl TokenAccount {
-    pub const LEN: usize = 32 + 8 + 8 + 8 + 32 + 32 + 8 + 1 + 1 + 8 + 8 + 200 + 8 + 8 + 1;
+    
+    // Create the platform-wide configuration account; only the program's upgrade authority may do so
+    pub fn initialize_platform_config(ctx: Context<InitializePlatformConfig>) -> Result<()> {
+        let platform_config = &mut ctx.accounts.platform_config;
+        let authority = &ctx.accounts.authority;
+        
+        platform_config.authority = authority.key();
+        platform_config.fee_tiers = Vec::new();
+        platform_config.bump = ctx.bumps.platform_config;
+        
+        emit!(PlatformConfigInitialized {
+            platform_config: platform_config.key(),
+            authority: authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+    
+    // Register a wallet allowed to attest holder KYC status
+    pub fn register_attester(
+        ctx: Context<RegisterAttester>,
+        attester: Pubkey,
+    ) -> Result<()> {
+        let attester_account = &mut ctx.accounts.attester_account;
+        let authority = &ctx.accounts.authority;
+        
+        attester_account.attester = attester;
+        attester_account.is_active = true;
+        attester_account.registered_at = Clock::get()?.unix_timestamp;
+        attester_account.bump = ctx.bumps.attester_account;
+        
+        emit!(AttesterRegistered {
+            attester,
+            authority: authority.key(),
+            timestamp: attester_account.registered_at,
+        });
+        
+        Ok(())
+    }
+    
+    // Deactivate a registered attester
+    pub fn revoke_attester(ctx: Context<RevokeAttester>) -> Result<()> {
+        let attester_account = &mut ctx.accounts.attester_account;
+        let authority = &ctx.accounts.authority;
+        
+        // Check if already revoked
+        require!(attester_account.is_active, ErrorCode::AttesterNotActive);
+        
+        attester_account.is_active = false;
+        
+        emit!(AttesterRevoked {
+            attester: attester_account.attester,
+            authority: authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+    
+    // Create the caller's holder record for a token, e.g. so an attester can verify it before the first buy
+    pub fn open_holder(ctx: Context<OpenHolder>) -> Result<()> {
+        let holder = &mut ctx.accounts.holder;
+        
+        holder.init_if_new(ctx.accounts.token_account.key(), ctx.accounts.owner.key(), ctx.bumps.holder);
+        
+        Ok(())
+    }
+    
+    // Mark a holder as KYC verified until the given timestamp
+    pub fn verify_holder(
+        ctx: Context<AttestHolder>,
+        expires_at: i64,
+    ) -> Result<()> {
+        let holder = &mut ctx.accounts.holder;
+        let attester = &ctx.accounts.attester;
+        
+        // Verification must expire in the future
+        require!(
+            expires_at > Clock::get()?.unix_timestamp,
+            ErrorCode::InvalidVerificationExpiry
+        );
+        
+        holder.is_verified = true;
+        holder.verification_expiry = expires_at;
+        
+        emit!(HolderVerified {
+            holder: holder.key(),
+            owner: holder.owner,
+            attester: attester.key(),
+            expires_at,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+    
+    // Remove a holder's KYC verification
+    pub fn revoke_holder_verification(ctx: Context<AttestHolder>) -> Result<()> {
+        let holder = &mut ctx.accounts.holder;
+        let attester = &ctx.accounts.attester;
+        
+        holder.is_verified = false;
+        holder.verification_expiry = 0;
+        
+        emit!(HolderVerificationRevoked {
+            holder: holder.key(),
+            owner: holder.owner,
+            attester: attester.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+    
+    // Opt a token in or out of verified-holders-only trading
+    pub fn set_verified_holders_only(
+        ctx: Context<UpdateTradingRules>,
+        enabled: bool,
+    ) -> Result<()> {
+        let token_account = &mut ctx.accounts.token_account;
+        let authority = &ctx.accounts.authority;
+        
+        // Security check: verify account is not frozen
+        require!(!token_account.is_frozen, ErrorCode::AccountFrozen);
+        
+        // Validate authority
+        require!(
+            token_account.authority == authority.key(),
+            ErrorCode::Unauthorized
+        );
+        
+        token_account.verified_holders_only = enabled;
+        token_account.last_updated_at = Clock::get()?.unix_timestamp;
+        
+        emit!(VerifiedHoldersOnlyUpdated {
+            token_account: token_account.key(),
+            enabled,
+            authority: authority.key(),
+            timestamp: token_account.last_updated_at,
+        });
+        
+        Ok(())
+    }
+    
+    // Configure the anti-sniper launch window, before any tokens are minted
+    pub fn configure_launch_guard(
+        ctx: Context<UpdateTradingRules>,
+        window_slots: u64,
+        max_wallet_buy: u64,
+        max_tx_buy: u64,
+        sniper_tax_bps: u64,
+    ) -> Result<()> {
+        let token_account = &mut ctx.accounts.token_account;
+        let authority = &ctx.accounts.authority;
+        
+        // Security check: verify account is not frozen
+        require!(!token_account.is_frozen, ErrorCode::AccountFrozen);
+        
+        // Validate authority
+        require!(
+            token_account.authority == authority.key(),
+            ErrorCode::Unauthorized
+        );
+        
+        // The guard cannot be changed once trading has started
+        require!(token_account.total_supply == 0, ErrorCode::LaunchGuardLocked);
+        
+        // Sniper tax plus the 1% treasury share must stay within the mint
+        require!(sniper_tax_bps <= MAX_SNIPER_TAX_BPS, ErrorCode::InvalidSniperTax);
+        
+        token_account.sniper_window_slots = window_slots;
+        token_account.sniper_max_wallet_buy = max_wallet_buy;
+        token_account.sniper_max_tx_buy = max_tx_buy;
+        token_account.sniper_tax_bps = sniper_tax_bps;
+        token_account.last_updated_at = Clock::get()?.unix_timestamp;
+        
+        emit!(LaunchGuardConfigured {
+            token_account: token_account.key(),
+            window_slots,
+            max_wallet_buy,
+            max_tx_buy,
+            sniper_tax_bps,
+            authority: authority.key(),
+            timestamp: token_account.last_updated_at,
+        });
+        
+        Ok(())
+    }
+    
+    // Set max wallet and max transaction limits as a share of a reference supply
+    pub fn set_trading_limits(
+        ctx: Context<UpdateTradingRules>,
+        max_wallet_bps: u64,
+        max_tx_bps: u64,
+        relax_after_graduation: bool,
+        reference_supply: u64,
+    ) -> Result<()> {
+        let token_account = &mut ctx.accounts.token_account;
+        let authority = &ctx.accounts.authority;
+        
+        // Security check: verify account is not frozen
+        require!(!token_account.is_frozen, ErrorCode::AccountFrozen);
+        
+        // Validate authority
+        require!(
+            token_account.authority == authority.key(),
+            ErrorCode::Unauthorized
+        );
+        
+        // Validate limits (0 disables a limit)
+        require!(
+            max_wallet_bps <= 10000 && max_tx_bps <= 10000,
+            ErrorCode::InvalidTradingLimits
+        );
+        
+        // Limits are shares of a fixed supply; the current supply starts at zero and grows with every buy
+        require!(
+            reference_supply > 0 || token_account.max_supply > 0 || (max_wallet_bps == 0 && max_tx_bps == 0),
+            ErrorCode::InvalidTradingLimits
+        );
+        
+        token_account.max_wallet_bps = max_wallet_bps;
+        token_account.max_tx_bps = max_tx_bps;
+        token_account.relax_limits_after_graduation = relax_after_graduation;
+        token_account.limit_reference_supply = reference_supply;
+        token_account.last_updated_at = Clock::get()?.unix_timestamp;
+        
+        emit!(TradingLimitsUpdated {
+            token_account: token_account.key(),
+            max_wallet_bps,
+            max_tx_bps,
+            relax_after_graduation,
+            reference_supply,
+            authority: authority.key(),
+            timestamp: token_account.last_updated_at,
+        });
+        
+        Ok(())
+    }
+    
+    // Set the minimum time a holder must wait between trades
+    pub fn set_trade_cooldown(
+        ctx: Context<UpdateTradingRules>,
+        cooldown_seconds: i64,
+    ) -> Result<()> {
+        let token_account = &mut ctx.accounts.token_account;
+        let authority = &ctx.accounts.authority;
+        
+        // Security check: verify account is not frozen
+        require!(!token_account.is_frozen, ErrorCode::AccountFrozen);
+        
+        // Validate authority
+        require!(
+            token_account.authority == authority.key(),
+            ErrorCode::Unauthorized
+        );
+        
+        // Validate cooldown (0 disables it)
+        require!(
+            (0..=MAX_TRADE_COOLDOWN_SECONDS).contains(&cooldown_seconds),
+            ErrorCode::InvalidTradeCooldown
+        );
+        
+        token_account.trade_cooldown_seconds = cooldown_seconds;
+        token_account.last_updated_at = Clock::get()?.unix_timestamp;
+        
+        emit!(TradeCooldownUpdated {
+            token_account: token_account.key(),
+            cooldown_seconds,
+            authority: authority.key(),
+            timestamp: token_account.last_updated_at,
+        });
+        
+        Ok(())
+    }
+    
+    // Configure the bonding curve reserves and graduation target, before any tokens are minted
+    pub fn configure_curve(
+        ctx: Context<UpdateTradingRules>,
+        virtual_sol_reserve: u64,
+        virtual_token_reserve: u64,
+        graduation_reserve_target: u64,
+    ) -> Result<()> {
+        let token_account = &mut ctx.accounts.token_account;
+        let authority = &ctx.accounts.authority;
+        
+        // Security check: verify account is not frozen
+        require!(!token_account.is_frozen, ErrorCode::AccountFrozen);
+        
+        // Validate authority
+        require!(
+            token_account.authority == authority.key(),
+            ErrorCode::Unauthorized
+        );
+        
+        // The curve cannot be reshaped once trading has started
+        require!(
+            token_account.total_supply == 0 && token_account.tokens_sold == 0,
+            ErrorCode::CurveLocked
+        );
+        
+        // Validate reserves and target
+        require!(
+            virtual_sol_reserve > 0 && virtual_token_reserve > 0 && graduation_reserve_target > 0,
+            ErrorCode::InvalidCurveConfig
+        );
+        
+        token_account.virtual_sol_reserve = virtual_sol_reserve;
+        token_account.virtual_token_reserve = virtual_token_reserve;
+        token_account.graduation_reserve_target = graduation_reserve_target;
+        token_account.check_curve_covers_max_supply()?;
+        token_account.last_updated_at = Clock::get()?.unix_timestamp;
+        
+        emit!(CurveConfigured {
+            token_account: token_account.key(),
+            virtual_sol_reserve,
+            virtual_token_reserve,
+            graduation_reserve_target,
+            authority: authority.key(),
+            timestamp: token_account.last_updated_at,
+        });
+        
+        Ok(())
+    }
+    
+    // Commit a share of future creator fees to buyback-and-burn
+    pub fn set_buyback(
+        ctx: Context<UpdateTradingRules>,
+        buyback_bps: u64,
+    ) -> Result<()> {
+        let token_account = &mut ctx.accounts.token_account;
+        let authority = &ctx.accounts.authority;
+        
+        // Security check: verify account is not frozen
+        require!(!token_account.is_frozen, ErrorCode::AccountFrozen);
+        
+        // Validate authority
+        require!(
+            token_account.authority == authority.key(),
+            ErrorCode::Unauthorized
+        );
+        
+        // Validate share (0 disables buybacks)
+        require!(buyback_bps <= 10000, ErrorCode::InvalidBuybackShare);
+        
+        token_account.buyback_bps = buyback_bps;
+        token_account.last_updated_at = Clock::get()?.unix_timestamp;
+        
+        emit!(BuybackConfigured {
+            token_account: token_account.key(),
+            buyback_bps,
+            authority: authority.key(),
+            timestamp: token_account.last_updated_at,
+        });
+        
+        Ok(())
+    }
+    
+    // Configure an allowlisted presale that runs before the public bonding curve
+    pub fn configure_presale(
+        ctx: Context<ConfigurePresale>,
+        merkle_root: [u8; 32],
+        max_per_wallet: u64,
+        price_per_token: u64,
+        start_time: i64,
+        end_time: i64,
+    ) -> Result<()> {
+        let token_account = &mut ctx.accounts.token_account;
+        let presale = &mut ctx.accounts.presale;
+        let authority = &ctx.accounts.authority;
+        let now = Clock::get()?.unix_timestamp;
+        
+        // Security check: verify account is not frozen
+        require!(!token_account.is_frozen, ErrorCode::AccountFrozen);
+        
+        // Validate authority
+        require!(
+            token_account.authority == authority.key(),
+            ErrorCode::Unauthorized
+        );
+        
+        // Presale must happen before the public curve opens
+        require!(token_account.total_supply == 0, ErrorCode::PresaleAfterLaunch);
+        
+        // Validate presale parameters
+        require!(
+            start_time < end_time && end_time > now,
+            ErrorCode::InvalidPresaleWindow
+        );
+        require!(
+            max_per_wallet > 0 && price_per_token > 0,
+            ErrorCode::InvalidPresaleConfig
+        );
+        
+        presale.token_account = token_account.key();
+        presale.merkle_root = merkle_root;
+        presale.max_per_wallet = max_per_wallet;
+        presale.price_per_token = price_per_token;
+        presale.start_time = start_time;
+        presale.end_time = end_time;
+        presale.tokens_sold = 0;
+        presale.proceeds = 0;
+        presale.bump = ctx.bumps.presale;
+        
+        token_account.presale_end_time = end_time;
+        token_account.last_updated_at = now;
+        
+        emit!(PresaleConfigured {
+            token_account: token_account.key(),
+            presale: presale.key(),
+            merkle_root,
+            max_per_wallet,
+            price_per_token,
+            start_time,
+            end_time,
+            authority: authority.key(),
+        });
+        
+        Ok(())
+    }
+    
+    // Buy tokens at the fixed presale price with a merkle proof of allowlisting
+    pub fn presale_buy(
+        ctx: Context<PresaleBuy>,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let token_account = &mut ctx.accounts.token_account;
+        let presale = &mut ctx.accounts.presale;
+        let receipt = &mut ctx.accounts.receipt;
+        let holder = &mut ctx.accounts.holder;
+        let buyer = &ctx.accounts.buyer;
+        let treasury = &ctx.accounts.treasury;
+        let now = Clock::get()?.unix_timestamp;
+        
+        holder.init_if_new(token_account.key(), buyer.key(), ctx.bumps.holder);
+        
+        // Security check: verify account is not frozen
+        require!(!token_account.is_frozen, ErrorCode::AccountFrozen);
+        
+        // Validate presale window
+        require!(
+            now >= presale.start_time && now < presale.end_time,
+            ErrorCode::PresaleNotActive
+        );
+        require!(amount > 0, ErrorCode::TradeBelowMinimum);
+        
+        // Buyer must prove membership in the allowlist
+        let leaf = keccak::hashv(&[buyer.key().as_ref()]).0;
+        require!(
+            verify_merkle_proof(&proof, presale.merkle_root, leaf),
+            ErrorCode::InvalidMerkleProof
+        );
+        
+        // KYC check: verified-only launches reject unverified buyers
+        if token_account.verified_holders_only {
+            require!(holder.is_verification_active(now), ErrorCode::HolderNotVerified);
+        }
+        
+        // Enforce the per-wallet presale cap
+        let purchased = receipt.amount_purchased.checked_add(amount)
+            .ok_or(ErrorCode::CalculationError)?;
+        require!(
+            purchased <= presale.max_per_wallet,
+            ErrorCode::PresaleWalletCapExceeded
+        );
+        
+        // Pay the fixed presale price into the treasury
+        let total_price = amount.checked_mul(presale.price_per_token)
+            .ok_or(ErrorCode::CalculationError)?;
+        invoke(
+            &system_instruction::transfer(&buyer.key(), &treasury.key(), total_price),
+            &[
+                buyer.to_account_info(),
+                treasury.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+        )?;
+        
+        // Update supply and holder balance
+        token_account.add_supply(amount)?;
+        let new_holder_balance = holder.balance.checked_add(amount)
+            .ok_or(ErrorCode::CalculationError)?;
+        
+        // Presale buys count against the same max transaction and max wallet limits as curve buys
+        token_account.check_holding_limits(amount, new_holder_balance)?;
+        
+        token_account.track_holder_balance(holder.balance, new_holder_balance)?;
+        holder.balance = new_holder_balance;
+        holder.record_trade(now);
+        token_account.check_supply_invariant()?;
+        mint_to_holder(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.mint.to_account_info(),
+            &ctx.accounts.mint_authority.to_account_info(),
+            token_account.key(),
+            token_account.mint_authority_bump,
+            &ctx.accounts.buyer_token_account.to_account_info(),
+            amount,
+        )?;
+        
+        // Track presale totals separately from the curve
+        receipt.presale = presale.key();
+        receipt.buyer = buyer.key();
+        receipt.amount_purchased = purchased;
+        receipt.bump = ctx.bumps.receipt;
+        
+        presale.tokens_sold = presale.tokens_sold.checked_add(amount)
+            .ok_or(ErrorCode::CalculationError)?;
+        presale.proceeds = presale.proceeds.checked_add(total_price)
+            .ok_or(ErrorCode::CalculationError)?;
+        
+        // Tokens launched through record_token_launch also keep presale totals on their launch record
+        if let Some(launch_record) = ctx.accounts.launch_record.as_mut() {
+            launch_record.presale_tokens_sold = launch_record.presale_tokens_sold.checked_add(amount)
+                .ok_or(ErrorCode::CalculationError)?;
+            launch_record.presale_proceeds = launch_record.presale_proceeds.checked_add(total_price)
+                .ok_or(ErrorCode::CalculationError)?;
+        }
+        
+        emit!(PresalePurchased {
+            token_account: token_account.key(),
+            presale: presale.key(),
+            buyer: buyer.key(),
+            holder: holder.key(),
+            amount,
+            price_per_token: presale.price_per_token,
+            total_price,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+    
+    // Lock tokens the creator holds in escrow with a cliff and linear unlock
+    pub fn create_vesting(
+        ctx: Context<CreateVesting>,
+        total_amount: u64,
+        cliff_seconds: i64,
+        duration_seconds: i64,
+    ) -> Result<()> {
+        let token_account = &mut ctx.accounts.token_account;
+        let vesting = &mut ctx.accounts.vesting_schedule;
+        let creator_holder = &mut ctx.accounts.creator_holder;
+        let authority = &ctx.accounts.authority;
+        let beneficiary = &ctx.accounts.beneficiary;
+        let now = Clock::get()?.unix_timestamp;
+        
+        // Security check: verify account is not frozen
+        require!(!token_account.is_frozen, ErrorCode::AccountFrozen);
+        
+        // Validate authority
+        require!(
+            token_account.authority == authority.key(),
+            ErrorCode::Unauthorized
+        );
+        
+        // Validate schedule
+        require!(total_amount > 0, ErrorCode::InvalidVestingSchedule);
+        require!(
+            cliff_seconds >= 0 && duration_seconds > 0 && cliff_seconds <= duration_seconds,
+            ErrorCode::InvalidVestingSchedule
+        );
+        
+        // Vesting never mints: the tokens leave the creator's balance and sit in escrow until released.
+        // Escrowed tokens are burned from the creator's token account and minted again on release.
+        token_account.escrow_vesting(creator_holder, total_amount)?;
+        token_account.check_supply_invariant()?;
+        burn_from_holder(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.mint.to_account_info(),
+            &ctx.accounts.authority_token_account.to_account_info(),
+            &authority.to_account_info(),
+            total_amount,
+        )?;
+        
+        vesting.token_account = token_account.key();
+        vesting.beneficiary = beneficiary.key();
+        vesting.total_amount = total_amount;
+        vesting.released_amount = 0;
+        vesting.start_time = now;
+        vesting.cliff_time = now.checked_add(cliff_seconds)
+            .ok_or(ErrorCode::CalculationError)?;
+        vesting.end_time = now.checked_add(duration_seconds)
+            .ok_or(ErrorCode::CalculationError)?;
+        vesting.bump = ctx.bumps.vesting_schedule;
+        
+        emit!(VestingCreated {
+            token_account: token_account.key(),
+            vesting_schedule: vesting.key(),
+            beneficiary: beneficiary.key(),
+            total_amount,
+            start_time: vesting.start_time,
+            cliff_time: vesting.cliff_time,
+            end_time: vesting.end_time,
+        });
+        
+        Ok(())
+    }
+    
+    // Release the unlocked portion of a vesting schedule to the beneficiary
+    pub fn release_vested(ctx: Context<ReleaseVested>) -> Result<()> {
+        let token_account = &mut ctx.accounts.token_account;
+        let vesting = &mut ctx.accounts.vesting_schedule;
+        let holder = &mut ctx.accounts.holder;
+        let now = Clock::get()?.unix_timestamp;
+        
+        holder.init_if_new(token_account.key(), vesting.beneficiary, ctx.bumps.holder);
+        
+        // Security check: verify account is not frozen
+        require!(!token_account.is_frozen, ErrorCode::AccountFrozen);
+        
+        let releasable = vesting.vested_amount(now)?
+            .checked_sub(vesting.released_amount)
+            .ok_or(ErrorCode::CalculationError)?;
+        require!(releasable > 0, ErrorCode::NothingToRelease);
+        
+        vesting.released_amount = vesting.released_amount.checked_add(releasable)
+            .ok_or(ErrorCode::CalculationError)?;
+        token_account.release_escrow(holder, releasable)?;
+        token_account.check_supply_invariant()?;
+        mint_to_holder(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.mint.to_account_info(),
+            &ctx.accounts.mint_authority.to_account_info(),
+            token_account.key(),
+            token_account.mint_authority_bump,
+            &ctx.accounts.beneficiary_token_account.to_account_info(),
+            releasable,
+        )?;
+        
+        emit!(VestingReleased {
+            token_account: token_account.key(),
+            vesting_schedule: vesting.key(),
+            beneficiary: vesting.beneficiary,
+            holder: holder.key(),
+            amount: releasable,
+            released_amount: vesting.released_amount,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+    
+    // Record the LP mint of the pool a graduated token migrated to; only its LP can be locked
+    pub fn set_pool_lp_mint(ctx: Context<SetPoolLpMint>) -> Result<()> {
+        let token_account = &mut ctx.accounts.token_account;
+        let lp_mint = &ctx.accounts.lp_mint;
+        
+        require!(!token_account.is_bonding_curve_active, ErrorCode::NotGraduated);
+        require!(
+            token_account.pool_lp_mint == Pubkey::default(),
+            ErrorCode::PoolLpMintAlreadySet
+        );
+        
+        token_account.pool_lp_mint = lp_mint.key();
+        
+        emit!(PoolLpMintRecorded {
+            token_account: token_account.key(),
+            lp_mint: lp_mint.key(),
+            authority: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+    
+    // Escrow LP tokens for a graduated token until the unlock time
+    pub fn lock_liquidity(
+        ctx: Context<LockLiquidity>,
+        amount: u64,
+        unlock_time: i64,
+    ) -> Result<()> {
+        let token_account = &ctx.accounts.token_account;
+        let liquidity_lock = &mut ctx.accounts.liquidity_lock;
+        let authority = &ctx.accounts.authority;
+        let now = Clock::get()?.unix_timestamp;
+        
+        // Validate authority
+        require!(
+            token_account.authority == authority.key(),
+            ErrorCode::Unauthorized
+        );
+        
+        // Liquidity only exists once the token has migrated off the curve
+        require!(!token_account.is_bonding_curve_active, ErrorCode::NotGraduated);
+        require!(
+            ctx.accounts.lp_mint.key() == token_account.pool_lp_mint,
+            ErrorCode::LpMintMismatch
+        );
+        
+        // Validate lock parameters
+        require!(amount > 0, ErrorCode::InvalidLiquidityLock);
+        require!(unlock_time > now, ErrorCode::InvalidLiquidityLock);
+        
+        // Move LP tokens into the program-owned vault
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.owner_lp_account.to_account_info(),
+                    mint: ctx.accounts.lp_mint.to_account_info(),
+                    to: ctx.accounts.lp_vault.to_account_info(),
+                    authority: authority.to_account_info(),
+                },
+            ),
+            amount,
+            ctx.accounts.lp_mint.decimals,
+        )?;
+        
+        liquidity_lock.token_account = token_account.key();
+        liquidity_lock.lp_mint = ctx.accounts.lp_mint.key();
+        liquidity_lock.owner = authority.key();
+        liquidity_lock.lp_vault = ctx.accounts.lp_vault.key();
+        liquidity_lock.amount = amount;
+        liquidity_lock.locked_at = now;
+        liquidity_lock.unlock_time = unlock_time;
+        liquidity_lock.bump = ctx.bumps.liquidity_lock;
+        
+        emit!(LiquidityLocked {
+            token_account: token_account.key(),
+            liquidity_lock: liquidity_lock.key(),
+            lp_mint: liquidity_lock.lp_mint,
+            owner: authority.key(),
+            amount,
+            unlock_time,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+    
+    // Push back the unlock time of an existing liquidity lock
+    pub fn extend_liquidity_lock(
+        ctx: Context<ExtendLiquidityLock>,
+        new_unlock_time: i64,
+    ) -> Result<()> {
+        let liquidity_lock = &mut ctx.accounts.liquidity_lock;
+        
+        // A lock can only ever be extended, never shortened
+        require!(
+            new_unlock_time > liquidity_lock.unlock_time,
+            ErrorCode::InvalidLiquidityLock
+        );
+        
+        let old_unlock_time = liquidity_lock.unlock_time;
+        liquidity_lock.unlock_time = new_unlock_time;
+        
+        emit!(LiquidityLockExtended {
+            token_account: liquidity_lock.token_account,
+            liquidity_lock: liquidity_lock.key(),
+            old_unlock_time,
+            new_unlock_time,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+    
+    // Return escrowed LP tokens to the owner after the unlock time, closing the lock and its vault
+    pub fn unlock_liquidity(ctx: Context<UnlockLiquidity>) -> Result<()> {
+        let liquidity_lock = &mut ctx.accounts.liquidity_lock;
+        let now = Clock::get()?.unix_timestamp;
+        
+        require!(now >= liquidity_lock.unlock_time, ErrorCode::TimeLockActive);
+        
+        let amount = ctx.accounts.lp_vault.amount;
+        let token_account_key = liquidity_lock.token_account;
+        let lp_mint_key = liquidity_lock.lp_mint;
+        let seeds: &[&[u8]] = &[
+            b"liquidity_lock",
+            token_account_key.as_ref(),
+            lp_mint_key.as_ref(),
+            &[liquidity_lock.bump],
+        ];
+        
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.lp_vault.to_account_info(),
+                    mint: ctx.accounts.lp_mint.to_account_info(),
+                    to: ctx.accounts.owner_lp_account.to_account_info(),
+                    authority: liquidity_lock.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+            ctx.accounts.lp_mint.decimals,
+        )?;
+        
+        // Refund the vault rent; the lock account is closed on exit so the LP can be locked again
+        token_interface::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.lp_vault.to_account_info(),
+                destination: ctx.accounts.owner.to_account_info(),
+                authority: liquidity_lock.to_account_info(),
+            },
+            &[seeds],
+        ))?;
+        
+        emit!(LiquidityUnlocked {
+            token_account: token_account_key,
+            liquidity_lock: liquidity_lock.key(),
+            lp_mint: lp_mint_key,
+            owner: liquidity_lock.owner,
+            amount,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+    
+    // Register a wallet allowed to verify launch records
+    pub fn register_verifier(
+        ctx: Context<RegisterVerifier>,
+        verifier: Pubkey,
+    ) -> Result<()> {
+        let verifier_account = &mut ctx.accounts.verifier_account;
+        let authority = &ctx.accounts.authority;
+        
+        verifier_account.verifier = verifier;
+        verifier_account.is_active = true;
+        verifier_account.registered_at = Clock::get()?.unix_timestamp;
+        verifier_account.bump = ctx.bumps.verifier_account;
+        
+        emit!(VerifierRegistered {
+            verifier,
+            authority: authority.key(),
+            timestamp: verifier_account.registered_at,
+        });
+        
+        Ok(())
+    }
+    
+    // Deactivate a registered verifier
+    pub fn revoke_verifier(ctx: Context<RevokeVerifier>) -> Result<()> {
+        let verifier_account = &mut ctx.accounts.verifier_account;
+        let authority = &ctx.accounts.authority;
+        
+        // Check if already revoked
+        require!(verifier_account.is_active, ErrorCode::VerifierNotActive);
+        
+        verifier_account.is_active = false;
+        
+        emit!(VerifierRevoked {
+            verifier: verifier_account.verifier,
+            authority: authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+    
+    // Mark a launch record as verified by a registered verifier
+    pub fn verify_launch_record(ctx: Context<VerifyLaunchRecord>) -> Result<()> {
+        let launch_record = &mut ctx.accounts.launch_record;
+        let verifier = &ctx.accounts.verifier;
+        
+        // Check if already verified
+        require!(!launch_record.is_verified, ErrorCode::LaunchAlreadyVerified);
+        
+        launch_record.is_verified = true;
+        launch_record.verified_by = verifier.key();
+        
+        emit!(LaunchRecordVerified {
+            token_account: launch_record.token_account,
+            launch_record: launch_record.key(),
+            verifier: verifier.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+    
+    // Register a partner wallet earning `share_bps` of the platform fee on referred buys and sells
+    pub fn register_referrer(
+        ctx: Context<RegisterReferrer>,
+        wallet: Pubkey,
+        share_bps: u64,
+    ) -> Result<()> {
+        let referrer_account = &mut ctx.accounts.referrer_account;
+        let authority = &ctx.accounts.authority;
+        
+        // Validate share
+        require!(share_bps <= 10000, ErrorCode::InvalidReferralShare);
+        
+        referrer_account.wallet = wallet;
+        referrer_account.share_bps = share_bps;
+        referrer_account.total_earned = 0;
+        referrer_account.total_claimed = 0;
+        referrer_account.is_active = true;
+        referrer_account.registered_at = Clock::get()?.unix_timestamp;
+        referrer_account.bump = ctx.bumps.referrer_account;
+        
+        emit!(ReferrerUpdated {
+            wallet,
+            share_bps,
+            is_active: true,
+            authority: authority.key(),
+            timestamp: referrer_account.registered_at,
+        });
+        
+        Ok(())
+    }
+    
+    // Change a referrer's share or deactivate it; earnings already made stay claimable
+    pub fn update_referrer(
+        ctx: Context<UpdateReferrer>,
+        share_bps: u64,
+        is_active: bool,
+    ) -> Result<()> {
+        let referrer_account = &mut ctx.accounts.referrer_account;
+        let authority = &ctx.accounts.authority;
+        
+        // Validate share
+        require!(share_bps <= 10000, ErrorCode::InvalidReferralShare);
+        
+        referrer_account.share_bps = share_bps;
+        referrer_account.is_active = is_active;
+        
+        emit!(ReferrerUpdated {
+            wallet: referrer_account.wallet,
+            share_bps,
+            is_active,
+            authority: authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+    
+    // Pay out a referrer's unclaimed earnings to its wallet
+    pub fn claim_referral_fees(ctx: Context<ClaimReferralFees>) -> Result<()> {
+        let referrer_account = &mut ctx.accounts.referrer_account;
+        let wallet = &ctx.accounts.wallet;
+        
+        let amount = referrer_account.claimable()?;
+        require!(amount > 0, ErrorCode::NoFeesToClaim);
+        
+        referrer_account.total_claimed = referrer_account.total_claimed.checked_add(amount)
+            .ok_or(ErrorCode::CalculationError)?;
+        move_lamports(&referrer_account.to_account_info(), &wallet.to_account_info(), amount)?;
+        
+        emit!(ReferralFeesClaimed {
+            wallet: wallet.key(),
+            amount,
+            total_earned: referrer_account.total_earned,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+    
+    // Replace the platform fee tiers keyed by a trader's trailing 30-day volume
+    pub fn set_fee_tiers(ctx: Context<UpdatePlatformConfig>, fee_tiers: Vec<FeeTier>) -> Result<()> {
+        let platform_config = &mut ctx.accounts.platform_config;
+        let authority = &ctx.accounts.authority;
+        
+        validate_fee_tiers(&fee_tiers)?;
+        platform_config.fee_tiers = fee_tiers.clone();
+        
+        emit!(FeeTiersUpdated {
+            fee_tiers,
+            authority: authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+    
+    // Split the creator fees among weighted recipients. The first split applies at once, after paying
+    // out the fees collected so far to the creator; changes to an active split wait out a timelock
+    pub fn set_fee_split(ctx: Context<SetFeeSplit>, shares: Vec<FeeShare>) -> Result<()> {
+        let token_account = &mut ctx.accounts.token_account;
+        let fee_split = &mut ctx.accounts.fee_split;
+        let creator_fee_vault = &mut ctx.accounts.creator_fee_vault;
+        let authority = &ctx.accounts.authority;
+        let now = Clock::get()?.unix_timestamp;
+        
+        // Security check: verify account is not frozen
+        require!(!token_account.is_frozen, ErrorCode::AccountFrozen);
+        
+        // Validate authority
+        require!(
+            token_account.authority == authority.key(),
+            ErrorCode::Unauthorized
+        );
+        
+        validate_fee_split(&shares)?;
+        
+        if token_account.fee_split_enabled {
+            // Recipients get the timelock to claim or react before their share changes
+            fee_split.pending_shares = shares.clone();
+            fee_split.pending_effective_at = now.checked_add(FEE_SPLIT_TIMELOCK_SECONDS)
+                .ok_or(ErrorCode::CalculationError)?;
+            
+            emit!(FeeSplitProposed {
+                token_account: token_account.key(),
+                shares,
+                effective_at: fee_split.pending_effective_at,
+                authority: authority.key(),
+                timestamp: now,
+            });
+            
+            return Ok(());
+        }
+        
+        // Fees collected before the split belong to the creator
+        creator_fee_vault.token_account = token_account.key();
+        creator_fee_vault.bump = ctx.bumps.creator_fee_vault;
+        let creator_payout = if creator_fee_vault.claimable()? > 0 {
+            withdraw_fees(creator_fee_vault, &authority.to_account_info())?
+        } else {
+            0
+        };
+        
+        fee_split.token_account = token_account.key();
+        fee_split.recipients = Vec::new();
+        fee_split.distributed = creator_fee_vault.released()?;
+        fee_split.pending_shares = Vec::new();
+        fee_split.pending_effective_at = 0;
+        fee_split.bump = ctx.bumps.fee_split;
+        fee_split.apply_shares(&shares)?;
+        token_account.fee_split_enabled = true;
+        token_account.last_updated_at = now;
+        
+        emit!(FeeSplitApplied {
+            token_account: token_account.key(),
+            shares,
+            creator_payout,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+    
+    // Apply a proposed fee split once its timelock has passed; fees collected until then follow the old weights
+    pub fn apply_fee_split(ctx: Context<ApplyFeeSplit>) -> Result<()> {
+        let token_account = &ctx.accounts.token_account;
+        let fee_split = &mut ctx.accounts.fee_split;
+        let creator_fee_vault = &ctx.accounts.creator_fee_vault;
+        let now = Clock::get()?.unix_timestamp;
+        
+        // Security check: verify account is not frozen
+        require!(!token_account.is_frozen, ErrorCode::AccountFrozen);
+        
+        require!(!fee_split.pending_shares.is_empty(), ErrorCode::NoPendingFeeSplit);
+        require!(now >= fee_split.pending_effective_at, ErrorCode::TimeLockActive);
+        
+        fee_split.allocate(creator_fee_vault.released()?)?;
+        let shares = std::mem::take(&mut fee_split.pending_shares);
+        fee_split.apply_shares(&shares)?;
+        fee_split.pending_effective_at = 0;
+        
+        emit!(FeeSplitApplied {
+            token_account: token_account.key(),
+            shares,
+            creator_payout: 0,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+    
+    // Claim the caller's share of the creator fees under the token's fee split
+    pub fn claim_fee_split(ctx: Context<ClaimFeeSplit>) -> Result<()> {
+        let token_account = &ctx.accounts.token_account;
+        let fee_split = &mut ctx.accounts.fee_split;
+        let creator_fee_vault = &mut ctx.accounts.creator_fee_vault;
+        let recipient = &ctx.accounts.recipient;
+        
+        // Security check: verify account is not frozen
+        require!(!token_account.is_frozen, ErrorCode::AccountFrozen);
+        
+        // Share out the fees released since the last claim, then pay the caller's balance
+        fee_split.allocate(creator_fee_vault.released()?)?;
+        let amount = fee_split.claim(recipient.key())?;
+        
+        creator_fee_vault.total_claimed = creator_fee_vault.total_claimed.checked_add(amount)
+            .ok_or(ErrorCode::CalculationError)?;
+        move_lamports(&creator_fee_vault.to_account_info(), &recipient.to_account_info(), amount)?;
+        
+        emit!(FeeSplitClaimed {
+            token_account: token_account.key(),
+            recipient: recipient.key(),
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(
+    name: String,
+    symbol: String,
+    metadata_uri: String,
+    metadata_hash: [u8; 32],
+    creator_fee: u64,
+    platform_fee: u64,
+    decimals: u8
+)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + TokenAccount::INIT_SPACE
+    )]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = authority,
+        mint::decimals = decimals,
+        mint::authority = mint_authority,
+        mint::token_program = token_program
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    /// CHECK: PDA that holds mint and metadata update authority
+    #[account(
+        seeds = [b"mint_authority", token_account.key().as_ref()],
+        bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+    /// CHECK: Metaplex metadata PDA, created by the metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", metadata_program.key().as_ref(), mint.key().as_ref()],
+        seeds::program = metadata_program.key(),
+        bump
+    )]
+    pub metadata: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK: This is the treasury wallet
+    pub treasury: AccountInfo<'info>,
+    // Token-2022 mints are created by initialize_token2022, with the extensions their fee and metadata instructions rely on
+    pub token_program: Program<'info, Token>,
+    pub metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeToken2022<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + TokenAccount::INIT_SPACE
+    )]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+    /// CHECK: Created and initialized as a Token-2022 mint in the instruction
+    #[account(mut)]
+    pub mint: Signer<'info>,
+    /// CHECK: PDA that holds mint, transfer fee and metadata authority
+    #[account(
+        seeds = [b"mint_authority", token_account.key().as_ref()],
+        bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK: This is the treasury wallet
+    pub treasury: AccountInfo<'info>,
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateFees<'info> {
+    #[account(mut)]
+    pub token_account: Account<'info, TokenAccount>,
+    pub authority: Signer<'info>,
+    /// CHECK: PDA holding the transfer fee config authority, only needed for Token-2022 mints
+    #[account(
+        seeds = [b"mint_authority", token_account.key().as_ref()],
+        bump = token_account.mint_authority_bump
+    )]
+    pub mint_authority: Option<UncheckedAccount<'info>>,
+    #[account(
+        mut,
+        address = token_account.mint @ ErrorCode::Unauthorized,
+        mint::token_program = token_program
+    )]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    pub token_program: Option<Program<'info, Token2022>>,
+}
+
+#[derive(Accounts)]
+pub struct EmergencyAction<'info> {
+    #[account(mut)]
+    pub token_account: Account<'info, TokenAccount>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MintTokens<'info> {
+    #[account(mut)]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+    /// CHECK: This is the treasury wallet receiving curve proceeds
+    #[account(mut)]
+    pub treasury: AccountInfo<'info>,
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + TokenHolder::INIT_SPACE,
+        seeds = [b"holder", token_account.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub holder: Account<'info, TokenHolder>,
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + TokenHolder::INIT_SPACE,
+        seeds = [b"holder", token_account.key().as_ref(), token_account.treasury.as_ref()],
+        bump
+    )]
+    pub treasury_holder: Account<'info, TokenHolder>,
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + FeeVault::INIT_SPACE,
+        seeds = [b"creator_fee_vault", token_account.key().as_ref()],
+        bump
+    )]
+    pub creator_fee_vault: Account<'info, FeeVault>,
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + FeeVault::INIT_SPACE,
+        seeds = [b"platform_fee_vault"],
+        bump
+    )]
+    pub platform_fee_vault: Account<'info, FeeVault>,
+    #[account(
+        mut,
+        seeds = [b"referrer", referrer.wallet.as_ref()],
+        bump = referrer.bump,
+        constraint = referrer.is_active @ ErrorCode::ReferrerNotActive
+    )]
+    pub referrer: Option<Account<'info, Referrer>>,
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + TraderStats::INIT_SPACE,
+        seeds = [b"trader_stats", buyer.key().as_ref()],
+        bump
+    )]
+    pub trader_stats: Box<Account<'info, TraderStats>>,
+    #[account(mut, address = token_account.mint @ ErrorCode::Unauthorized)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    /// CHECK: PDA holding the mint authority
+    #[account(
+        seeds = [b"mint_authority", token_account.key().as_ref()],
+        bump = token_account.mint_authority_bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = token_program
+    )]
+    pub buyer_token_account: Box<InterfaceAccount<'info, SplTokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program
+    )]
+    pub treasury_token_account: Box<InterfaceAccount<'info, SplTokenAccount>>,
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    #[account(address = token_account.token_program @ ErrorCode::Unauthorized)]
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SellTokens<'info> {
+    #[account(mut)]
+    pub token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"holder", token_account.key().as_ref(), seller.key().as_ref()],
+        bump = holder.bump
+    )]
+    pub holder: Account<'info, TokenHolder>,
+    #[account(
+        mut,
+        seeds = [b"creator_fee_vault", token_account.key().as_ref()],
+        bump = creator_fee_vault.bump
+    )]
+    pub creator_fee_vault: Account<'info, FeeVault>,
+    #[account(
+        mut,
+        seeds = [b"platform_fee_vault"],
+        bump = platform_fee_vault.bump
+    )]
+    pub platform_fee_vault: Account<'info, FeeVault>,
+    #[account(
+        mut,
+        seeds = [b"referrer", referrer.wallet.as_ref()],
+        bump = referrer.bump,
+        constraint = referrer.is_active @ ErrorCode::ReferrerNotActive
+    )]
+    pub referrer: Option<Account<'info, Referrer>>,
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    #[account(
+        init_if_needed,
+        payer = seller,
+        space = 8 + TraderStats::INIT_SPACE,
+        seeds = [b"trader_stats", seller.key().as_ref()],
+        bump
+    )]
+    pub trader_stats: Account<'info, TraderStats>,
+    #[account(mut)]
+    pub seller: Signer<'info>,
+    #[account(mut, address = token_account.mint @ ErrorCode::Unauthorized)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = seller,
+        token::token_program = token_program
+    )]
+    pub seller_token_account: Box<InterfaceAccount<'info, SplTokenAccount>>,
+    #[account(address = token_account.token_program @ ErrorCode::Unauthorized)]
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct BurnTokens<'info> {
+    #[account(mut)]
+    pub token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"holder", token_account.key().as_ref(), owner.key().as_ref()],
+        bump = holder.bump
+    )]
+    pub holder: Account<'info, TokenHolder>,
+    pub owner: Signer<'info>,
+    #[account(mut, address = token_account.mint @ ErrorCode::Unauthorized)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = owner,
+        token::token_program = token_program
+    )]
+    pub owner_token_account: InterfaceAccount<'info, SplTokenAccount>,
+    #[account(address = token_account.token_program @ ErrorCode::Unauthorized)]
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteBuyback<'info> {
+    #[account(mut)]
+    pub token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"creator_fee_vault", token_account.key().as_ref()],
+        bump = creator_fee_vault.bump
+    )]
+    pub creator_fee_vault: Account<'info, FeeVault>,
+    pub cranker: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct QuoteTrade<'info> {
+    pub token_account: Account<'info, TokenAccount>,
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Option<Account<'info, PlatformConfig>>,
+    #[account(
+        seeds = [b"trader_stats", trader.key().as_ref()],
+        bump = trader_stats.bump
+    )]
+    pub trader_stats: Option<Account<'info, TraderStats>>,
+    /// CHECK: The wallet being quoted; only used to derive its stats PDA
+    pub trader: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteTrade<'info> {
+    #[account(mut)]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [b"holder", token_account.key().as_ref(), seller_wallet.key().as_ref()],
+        bump = seller.bump
+    )]
+    pub seller: Account<'info, TokenHolder>,
+    #[account(
+        mut,
+        seeds = [b"holder", token_account.key().as_ref(), buyer_wallet.key().as_ref()],
+        bump = buyer.bump
+    )]
+    pub buyer: Account<'info, TokenHolder>,
+    #[account(mut)]
+    pub seller_wallet: Signer<'info>,
+    #[account(mut)]
+    pub buyer_wallet: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = buyer_wallet,
+        space = 8 + FeeVault::INIT_SPACE,
+        seeds = [b"creator_fee_vault", token_account.key().as_ref()],
+        bump
+    )]
+    pub creator_fee_vault: Account<'info, FeeVault>,
+    #[account(
+        init_if_needed,
+        payer = buyer_wallet,
+        space = 8 + FeeVault::INIT_SPACE,
+        seeds = [b"platform_fee_vault"],
+        bump
+    )]
+    pub platform_fee_vault: Account<'info, FeeVault>,
+    #[account(
+        mut,
+        seeds = [b"referrer", referrer.wallet.as_ref()],
+        bump = referrer.bump,
+        constraint = referrer.is_active @ ErrorCode::ReferrerNotActive
+    )]
+    pub referrer: Option<Account<'info, Referrer>>,
+    /// CHECK: This is the treasury wallet for fees
+    pub treasury: AccountInfo<'info>,
+    /// CHECK: This is the creator wallet for fees
+    pub creator: AccountInfo<'info>,
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    #[account(
+        init_if_needed,
+        payer = seller_wallet,
+        space = 8 + TraderStats::INIT_SPACE,
+        seeds = [b"trader_stats", seller_wallet.key().as_ref()],
+        bump
+    )]
+    pub seller_stats: Box<Account<'info, TraderStats>>,
+    #[account(
+        init_if_needed,
+        payer = buyer_wallet,
+        space = 8 + TraderStats::INIT_SPACE,
+        seeds = [b"trader_stats", buyer_wallet.key().as_ref()],
+        bump
+    )]
+    pub buyer_stats: Box<Account<'info, TraderStats>>,
+    #[account(
+        init_if_needed,
+        payer = buyer_wallet,
+        space = 8 + TokenHolder::INIT_SPACE,
+        seeds = [b"holder", token_account.key().as_ref(), token_account.treasury.as_ref()],
+        bump
+    )]
+    pub treasury_holder: Option<Box<Account<'info, TokenHolder>>>,
+    #[account(address = token_account.mint @ ErrorCode::Unauthorized)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = seller_wallet,
+        token::token_program = token_program
+    )]
+    pub seller_token_account: Box<InterfaceAccount<'info, SplTokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = buyer_wallet,
+        associated_token::mint = mint,
+        associated_token::authority = buyer_wallet,
+        associated_token::token_program = token_program
+    )]
+    pub buyer_token_account: Box<InterfaceAccount<'info, SplTokenAccount>>,
+    #[account(address = token_account.token_program @ ErrorCode::Unauthorized)]
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateTreasury<'info> {
+    #[account(mut)]
+    pub token_account: Account<'info, TokenAccount>,
+    pub authority: Signer<'info>,
+    /// CHECK: This is the current treasury wallet
+    pub treasury: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimCreatorFees<'info> {
+    pub token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"creator_fee_vault", token_account.key().as_ref()],
+        bump = creator_fee_vault.bump
+    )]
+    pub creator_fee_vault: Account<'info, FeeVault>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimPlatformFees<'info> {
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    #[account(
+        mut,
+        seeds = [b"platform_fee_vault"],
+        bump = platform_fee_vault.bump
+    )]
+    pub platform_fee_vault: Account<'info, FeeVault>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawTransferFees<'info> {
+    #[account(constraint = token_account.token_program == spl_token_2022::ID @ ErrorCode::NotToken2022)]
+    pub token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        address = token_account.mint @ ErrorCode::Unauthorized,
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: PDA holding the withdraw-withheld authority of the mint
+    #[account(
+        seeds = [b"mint_authority", token_account.key().as_ref()],
+        bump = token_account.mint_authority_bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = token_account.treasury,
+        token::token_program = token_program
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, SplTokenAccount>,
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct RecordTokenLaunch<'info> {
+    pub token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + LaunchRecord::INIT_SPACE,
+        seeds = [b"launch_record", token_account.key().as_ref()],
+        bump
+    )]
+    pub launch_record: Account<'info, LaunchRecord>,
+    pub system_program: Program<'info, System>,
+}
+
+// New account validation struct for token verification
+#[derive(Accounts)]
+pub struct VerifyTokenStats<'info> {
+    pub token_account: Account<'info, TokenAccount>,
+    /// CHECK: This is the oracle that verifies the token stats
+    pub oracle: AccountInfo<'info>,
+    pub authority: Signer<'info>,
+}
+
+// New account validation struct for updating metadata
+#[derive(Accounts)]
+pub struct UpdateTokenMetadata<'info> {
+    #[account(mut)]
+    pub token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK: PDA that holds metadata update authority
+    #[account(
+        seeds = [b"mint_authority", token_account.key().as_ref()],
+        bump = token_account.mint_authority_bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = token_account.mint @ ErrorCode::Unauthorized,
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: Metaplex metadata PDA, only needed for legacy SPL mints
+    #[account(
+        mut,
+        seeds = [b"metadata", Metadata::id().as_ref(), token_account.mint.as_ref()],
+        seeds::program = Metadata::id(),
+        bump
+    )]
+    pub metadata: Option<UncheckedAccount<'info>>,
+    pub metadata_program: Option<Program<'info, Metadata>>,
+    #[account(address = token_account.token_program @ ErrorCode::Unauthorized)]
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateTokenAccount<'info> {
+    /// CHECK: Legacy layout, validated and deserialized in the instruction
+    #[account(mut, owner = crate::ID)]
+    pub token_account: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AttachMint<'info> {
+    #[account(mut)]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = authority,
+        mint::decimals = token_account.decimals,
+        mint::authority = mint_authority,
+        mint::token_program = token_program
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    /// CHECK: PDA that holds mint and metadata update authority
+    #[account(
+        seeds = [b"mint_authority", token_account.key().as_ref()],
+        bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+    /// CHECK: Metaplex metadata PDA, created by the metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", metadata_program.key().as_ref(), mint.key().as_ref()],
+        seeds::program = metadata_program.key(),
+        bump
+    )]
+    pub metadata: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateTokenHolder<'info> {
+    #[account(
+        mut,
+        constraint = token_account.mint != Pubkey::default() @ ErrorCode::MintNotAttached
+    )]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+    /// CHECK: Legacy layout, validated and deserialized in the instruction, then closed
+    #[account(mut, owner = crate::ID)]
+    pub legacy_holder: UncheckedAccount<'info>,
+    /// CHECK: Wallet that owns the legacy holder, checked against it in the instruction
+    pub owner: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + TokenHolder::INIT_SPACE,
+        seeds = [b"holder", token_account.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub holder: Box<Account<'info, TokenHolder>>,
+    #[account(mut, address = token_account.mint @ ErrorCode::Unauthorized)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    /// CHECK: PDA holding the mint authority
+    #[account(
+        seeds = [b"mint_authority", token_account.key().as_ref()],
+        bump = token_account.mint_authority_bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program
+    )]
+    pub owner_token_account: Box<InterfaceAccount<'info, SplTokenAccount>>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(address = token_account.token_program @ ErrorCode::Unauthorized)]
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+// New account validation struct for transferring ownership
+#[derive(Accounts)]
+pub struct TransferOwnership<'info> {
+    #[account(mut)]
+    pub token_account: Account<'info, TokenAccount>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializePlatformConfig<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PlatformConfig::INIT_SPACE,
+        seeds = [b"platform_config"],
+        bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    // Only the program's upgrade authority can claim the platform authority role
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()) @ ErrorCode::Unauthorized)]
+    pub program: Program<'info, crate::program::WybeTokenProgram>,
+    #[account(constraint = program_data.upgrade_authority_address == Some(authority.key()) @ ErrorCode::Unauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(attester: Pubkey)]
+pub struct RegisterAttester<'info> {
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Attester::INIT_SPACE,
+        seeds = [b"attester", attester.as_ref()],
+        bump
+    )]
+    pub attester_account: Account<'info, Attester>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeAttester<'info> {
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    #[account(
+        mut,
+        seeds = [b"attester", attester_account.attester.as_ref()],
+        bump = attester_account.bump
+    )]
+    pub attester_account: Account<'info, Attester>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct OpenHolder<'info> {
+    pub token_account: Account<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + TokenHolder::INIT_SPACE,
+        seeds = [b"holder", token_account.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub holder: Account<'info, TokenHolder>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+// Account validation struct for KYC attestations on a holder
+#[derive(Accounts)]
+pub struct AttestHolder<'info> {
+    #[account(
+        seeds = [b"attester", attester.key().as_ref()],
+        bump = attester_account.bump,
+        constraint = attester_account.is_active @ ErrorCode::AttesterNotActive
+    )]
+    pub attester_account: Account<'info, Attester>,
+    pub attester: Signer<'info>,
+    #[account(mut)]
+    pub holder: Account<'info, TokenHolder>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigurePresale<'info> {
+    #[account(mut)]
+    pub token_account: Account<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PresaleConfig::INIT_SPACE,
+        seeds = [b"presale", token_account.key().as_ref()],
+        bump
+    )]
+    pub presale: Account<'info, PresaleConfig>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PresaleBuy<'info> {
+    #[account(mut)]
+    pub token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"presale", token_account.key().as_ref()],
+        bump = presale.bump
+    )]
+    pub presale: Account<'info, PresaleConfig>,
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + PresaleReceipt::INIT_SPACE,
+        seeds = [b"presale_receipt", presale.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub receipt: Account<'info, PresaleReceipt>,
+    #[account(
+        mut,
+        seeds = [b"launch_record", token_account.key().as_ref()],
+        bump = launch_record.bump,
+        constraint = launch_record.token_account == token_account.key() @ ErrorCode::InvalidLaunchRecord
+    )]
+    pub launch_record: Option<Account<'info, LaunchRecord>>,
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + TokenHolder::INIT_SPACE,
+        seeds = [b"holder", token_account.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub holder: Account<'info, TokenHolder>,
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    /// CHECK: This is the treasury wallet receiving presale proceeds
+    #[account(mut, address = token_account.treasury @ ErrorCode::Unauthorized)]
+    pub treasury: AccountInfo<'info>,
+    #[account(mut, address = token_account.mint @ ErrorCode::Unauthorized)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    /// CHECK: PDA holding the mint authority
+    #[account(
+        seeds = [b"mint_authority", token_account.key().as_ref()],
+        bump = token_account.mint_authority_bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = token_program
+    )]
+    pub buyer_token_account: Box<InterfaceAccount<'info, SplTokenAccount>>,
+    #[account(address = token_account.token_program @ ErrorCode::Unauthorized)]
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateVesting<'info> {
+    #[account(mut)]
+    pub token_account: Account<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + VestingSchedule::INIT_SPACE,
+        seeds = [b"vesting", token_account.key().as_ref(), beneficiary.key().as_ref()],
+        bump
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+    #[account(
+        mut,
+        seeds = [b"holder", token_account.key().as_ref(), authority.key().as_ref()],
+        bump = creator_holder.bump
+    )]
+    pub creator_holder: Account<'info, TokenHolder>,
+    /// CHECK: This is the wallet the vested tokens are released to
+    pub beneficiary: AccountInfo<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(mut, address = token_account.mint @ ErrorCode::Unauthorized)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = authority,
+        token::token_program = token_program
+    )]
+    pub authority_token_account: Box<InterfaceAccount<'info, SplTokenAccount>>,
+    #[account(address = token_account.token_program @ ErrorCode::Unauthorized)]
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseVested<'info> {
+    #[account(mut)]
+    pub token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"vesting", token_account.key().as_ref(), vesting_schedule.beneficiary.as_ref()],
+        bump = vesting_schedule.bump,
+        constraint = vesting_schedule.token_account == token_account.key() @ ErrorCode::Unauthorized
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + TokenHolder::INIT_SPACE,
+        seeds = [b"holder", token_account.key().as_ref(), vesting_schedule.beneficiary.as_ref()],
+        bump
+    )]
+    pub holder: Account<'info, TokenHolder>,
+    /// CHECK: This is the wallet the vested tokens are released to
+    #[account(address = vesting_schedule.beneficiary @ ErrorCode::Unauthorized)]
+    pub beneficiary: AccountInfo<'info>,
+    #[account(mut, address = token_account.mint @ ErrorCode::Unauthorized)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    /// CHECK: PDA holding the mint authority
+    #[account(
+        seeds = [b"mint_authority", token_account.key().as_ref()],
+        bump = token_account.mint_authority_bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = beneficiary,
+        associated_token::token_program = token_program
+    )]
+    pub beneficiary_token_account: Box<InterfaceAccount<'info, SplTokenAccount>>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(address = token_account.token_program @ ErrorCode::Unauthorized)]
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetPoolLpMint<'info> {
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    #[account(mut)]
+    pub token_account: Account<'info, TokenAccount>,
+    pub lp_mint: InterfaceAccount<'info, Mint>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct LockLiquidity<'info> {
+    pub token_account: Account<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + LiquidityLock::INIT_SPACE,
+        seeds = [b"liquidity_lock", token_account.key().as_ref(), lp_mint.key().as_ref()],
+        bump
+    )]
+    pub liquidity_lock: Account<'info, LiquidityLock>,
+    #[account(mint::token_program = token_program)]
+    pub lp_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"liquidity_vault", liquidity_lock.key().as_ref()],
+        bump,
+        token::mint = lp_mint,
+        token::authority = liquidity_lock,
+        token::token_program = token_program
+    )]
+    pub lp_vault: InterfaceAccount<'info, SplTokenAccount>,
+    #[account(
+        mut,
+        token::mint = lp_mint,
+        token::authority = authority,
+        token::token_program = token_program
+    )]
+    pub owner_lp_account: InterfaceAccount<'info, SplTokenAccount>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ExtendLiquidityLock<'info> {
+    #[account(
+        mut,
+        seeds = [b"liquidity_lock", liquidity_lock.token_account.as_ref(), liquidity_lock.lp_mint.as_ref()],
+        bump = liquidity_lock.bump,
+        has_one = owner @ ErrorCode::Unauthorized
+    )]
+    pub liquidity_lock: Account<'info, LiquidityLock>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UnlockLiquidity<'info> {
+    #[account(
+        mut,
+        seeds = [b"liquidity_lock", liquidity_lock.token_account.as_ref(), liquidity_lock.lp_mint.as_ref()],
+        bump = liquidity_lock.bump,
+        has_one = owner @ ErrorCode::Unauthorized,
+        has_one = lp_vault,
+        has_one = lp_mint,
+        close = owner
+    )]
+    pub liquidity_lock: Account<'info, LiquidityLock>,
+    #[account(mint::token_program = token_program)]
+    pub lp_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub lp_vault: InterfaceAccount<'info, SplTokenAccount>,
+    #[account(
+        mut,
+        token::mint = lp_mint,
+        token::authority = owner,
+        token::token_program = token_program
+    )]
+    pub owner_lp_account: InterfaceAccount<'info, SplTokenAccount>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+#[instruction(verifier: Pubkey)]
+pub struct RegisterVerifier<'info> {
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Verifier::INIT_SPACE,
+        seeds = [b"verifier", verifier.as_ref()],
+        bump
+    )]
+    pub verifier_account: Account<'info, Verifier>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeVerifier<'info> {
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    #[account(
+        mut,
+        seeds = [b"verifier", verifier_account.verifier.as_ref()],
+        bump = verifier_account.bump
+    )]
+    pub verifier_account: Account<'info, Verifier>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyLaunchRecord<'info> {
+    #[account(
+        mut,
+        seeds = [b"launch_record", launch_record.token_account.as_ref()],
+        bump = launch_record.bump
+    )]
+    pub launch_record: Account<'info, LaunchRecord>,
+    #[account(
+        seeds = [b"verifier", verifier.key().as_ref()],
+        bump = verifier_account.bump,
+        constraint = verifier_account.is_active @ ErrorCode::VerifierNotActive
+    )]
+    pub verifier_account: Account<'info, Verifier>,
+    pub verifier: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetFeeSplit<'info> {
+    #[account(mut)]
+    pub token_account: Account<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + FeeSplit::INIT_SPACE,
+        seeds = [b"fee_split", token_account.key().as_ref()],
+        bump
+    )]
+    pub fee_split: Account<'info, FeeSplit>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + FeeVault::INIT_SPACE,
+        seeds = [b"creator_fee_vault", token_account.key().as_ref()],
+        bump
+    )]
+    pub creator_fee_vault: Account<'info, FeeVault>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApplyFeeSplit<'info> {
+    pub token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"fee_split", token_account.key().as_ref()],
+        bump = fee_split.bump
+    )]
+    pub fee_split: Account<'info, FeeSplit>,
+    #[account(
+        seeds = [b"creator_fee_vault", token_account.key().as_ref()],
+        bump = creator_fee_vault.bump
+    )]
+    pub creator_fee_vault: Account<'info, FeeVault>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimFeeSplit<'info> {
+    pub token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"fee_split", token_account.key().as_ref()],
+        bump = fee_split.bump
+    )]
+    pub fee_split: Account<'info, FeeSplit>,
+    #[account(
+        mut,
+        seeds = [b"creator_fee_vault", token_account.key().as_ref()],
+        bump = creator_fee_vault.bump
+    )]
+    pub creator_fee_vault: Account<'info, FeeVault>,
+    #[account(mut)]
+    pub recipient: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdatePlatformConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct RegisterReferrer<'info> {
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Referrer::INIT_SPACE,
+        seeds = [b"referrer", wallet.as_ref()],
+        bump
+    )]
+    pub referrer_account: Account<'info, Referrer>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateReferrer<'info> {
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    #[account(
+        mut,
+        seeds = [b"referrer", referrer_account.wallet.as_ref()],
+        bump = referrer_account.bump
+    )]
+    pub referrer_account: Account<'info, Referrer>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimReferralFees<'info> {
+    #[account(
+        mut,
+        seeds = [b"referrer", wallet.key().as_ref()],
+        bump = referrer_account.bump,
+        has_one = wallet @ ErrorCode::Unauthorized
+    )]
+    pub referrer_account: Account<'info, Referrer>,
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+}
+
+// Account validation struct for authority-controlled trading rules
+#[derive(Accounts)]
+pub struct UpdateTradingRules<'info> {
+    #[account(mut)]
+    pub token_account: Account<'info, TokenAccount>,
+    pub authority: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct TokenAccount {
+    pub version: u8,                 // Account layout version
+    #[max_len(32)]
+    pub name: String,                // 32 bytes max
+    #[max_len(8)]
+    pub symbol: String,              // 8 bytes max
+    pub creator_fee: u64,            // Fee in basis points (1/100 of 1%)
+    pub platform_fee: u64,           // Fee in basis points (1/100 of 1%)
+    pub authority: Pubkey,           // 32 bytes
+    pub treasury: Pubkey,            // 32 bytes - treasury wallet address (can be hardware wallet)
+    pub total_supply: u64,           // Total supply of tokens
+    pub is_frozen: bool,             // Emergency freeze flag
+    pub is_bonding_curve_active: bool, // Whether bonding curve is active or not
+    pub market_cap: u64,             // Circulating supply valued at the current curve price (in lamports)
+    #[max_len(200)]
+    pub metadata_uri: String,        // Token metadata URI (200 bytes max)
+    pub metadata_hash: [u8; 32],     // Hash of the metadata JSON the URI points to
+    pub last_updated_at: i64,        // Last time the token was updated
+    pub creation_time: i64,          // Time when token was created
+    pub verified: bool,              // Whether token is verified
+    pub verified_holders_only: bool, // Only KYC verified holders may buy or trade
+    pub creation_slot: u64,          // Slot when token was created
+    pub sniper_window_slots: u64,    // Launch window length in slots (0 = disabled)
+    pub sniper_max_wallet_buy: u64,  // Max tokens per wallet during launch window (0 = no cap)
+    pub sniper_max_tx_buy: u64,      // Max tokens per buy during launch window (0 = no cap)
+    pub sniper_tax_bps: u64,         // Extra treasury tax on launch window buys in basis points
+    pub max_wallet_bps: u64,         // Max share of total supply per holder in basis points (0 = no cap)
+    pub max_tx_bps: u64,             // Max share of total supply per buy or trade in basis points (0 = no cap)
+    pub relax_limits_after_graduation: bool, // Drop wallet and transaction limits once the curve completes
+    pub limit_reference_supply: u64, // Supply the wallet and transaction limits are shares of
+    pub trade_cooldown_seconds: i64, // Min seconds between trades by the same holder (0 = disabled)
+    pub presale_end_time: i64,       // Public curve opens after this time (0 = no presale)
+    pub holder_count: u32,           // Number of holders with a non-zero balance
+    pub mint: Pubkey,                // SPL mint backing the token
+    pub mint_authority_bump: u8,     // Bump of the mint authority PDA
+    pub token_program: Pubkey,       // SPL Token or Token-2022 program owning the mint
+    pub metadata_locked: bool,       // Metadata can no longer be updated once set
+    pub virtual_sol_reserve: u64,    // Virtual SOL reserve of the constant-product curve (in lamports)
+    pub virtual_token_reserve: u64,  // Virtual token reserve of the constant-product curve
+    pub real_sol_reserve: u64,       // Lamports paid into the curve, held by this account
+    pub tokens_sold: u64,            // Tokens bought from the curve and not sold back
+    pub graduation_reserve_target: u64, // Real SOL reserve at which the curve graduates (in lamports)
+    pub decimals: u8,                // Decimals of the mint; amounts are in base units
+    pub max_supply: u64,             // Hard cap on total supply in base units (0 = uncapped legacy token)
+    pub buyback_bps: u64,            // Share of creator fees committed to buyback-and-burn in basis points
+    pub fee_split_enabled: bool,     // Creator fees are claimed by the FeeSplit recipients instead of the creator
+    pub total_held: u64,             // Sum of all holder balances
+    pub total_escrowed: u64,         // Tokens held in vesting escrow, counted in total supply but in no balance
+    pub pool_lp_mint: Pubkey,        // LP mint of the pool the token migrated to (default until graduation)
+    pub legacy_supply: u64,          // v1 supply not yet credited to a migrated holder balance
+    pub reserved: [u8; 32],          // Padding for future fields without a realloc
+}
+
+impl TokenAccount {
+    // Upgrades a v1 token account, which had no version byte, to the current layout
+    pub fn from_v1(v1: TokenAccountV1) -> Self {
+        Self {
+            version: CURRENT_ACCOUNT_VERSION,
+            name: v1.name,
+            symbol: v1.symbol,
+            creator_fee: v1.creator_fee,
+            platform_fee: v1.platform_fee,
+            authority: v1.authority,
+            treasury: v1.treasury,
+            total_supply: v1.total_supply,
+            is_frozen: v1.is_frozen,
+            is_bonding_curve_active: v1.is_bonding_curve_active,
+            market_cap: v1.market_cap,
+            metadata_uri: v1.metadata_uri,
+            metadata_hash: [0u8; 32],
+            last_updated_at: v1.last_updated_at,
+            creation_time: v1.creation_time,
+            verified: v1.verified,
+            verified_holders_only: false,
+            creation_slot: 0,
+            sniper_window_slots: 0,
+            sniper_max_wallet_buy: 0,
+            sniper_max_tx_buy: 0,
+            sniper_tax_bps: 0,
+            max_wallet_bps: 0,
+            max_tx_bps: 0,
+            relax_limits_after_graduation: false,
+            limit_reference_supply: 0,
+            trade_cooldown_seconds: 0,
+            presale_end_time: 0,
+            holder_count: 0,
+            mint: Pubkey::default(),
+            mint_authority_bump: 0,
+            token_program: Pubkey::default(),
+            metadata_locked: false,
+            virtual_sol_reserve: DEFAULT_VIRTUAL_SOL_RESERVE,
+            virtual_token_reserve: default_virtual_token_reserve(TOKEN_DECIMALS),
+            real_sol_reserve: 0,
+            tokens_sold: 0,
+            graduation_reserve_target: DEFAULT_GRADUATION_RESERVE_TARGET,
+            decimals: TOKEN_DECIMALS,
+            max_supply: 0,
+            buyback_bps: 0,
+            fee_split_enabled: false,
+            // v1 balances count towards total_held as their holders are migrated; the v1 treasury
+            // share was never credited to a holder and stays in the legacy supply
+            total_held: 0,
+            total_escrowed: 0,
+            pool_lp_mint: Pubkey::default(),
+            legacy_supply: v1.total_supply,
+            reserved: [0u8; 32],
+        }
+    }
+
+    pub fn is_in_launch_window(&self, slot: u64) -> bool {
+        self.sniper_window_slots > 0
+            && slot < self.creation_slot.saturating_add(self.sniper_window_slots)
+    }
+
+    // Resets trading and launch state for a freshly created token whose decimals are set
+    pub fn init_launch_state(&mut self, clock: &Clock) {
+        self.version = CURRENT_ACCOUNT_VERSION;
+        self.total_supply = 0;
+        self.is_frozen = false;
+        self.is_bonding_curve_active = true;
+        self.market_cap = 0;
+        self.last_updated_at = 0;
+        self.creation_time = clock.unix_timestamp;
+        self.creation_slot = clock.slot;
+        self.verified = false;
+        self.verified_holders_only = false;
+        self.sniper_window_slots = 0;
+        self.sniper_max_wallet_buy = 0;
+        self.sniper_max_tx_buy = 0;
+        self.sniper_tax_bps = 0;
+        self.max_wallet_bps = 0;
+        self.max_tx_bps = 0;
+        self.relax_limits_after_graduation = false;
+        self.limit_reference_supply = 0;
+        self.trade_cooldown_seconds = 0;
+        self.presale_end_time = 0;
+        self.holder_count = 0;
+        self.metadata_locked = false;
+        self.virtual_sol_reserve = DEFAULT_VIRTUAL_SOL_RESERVE;
+        self.virtual_token_reserve = default_virtual_token_reserve(self.decimals);
+        self.real_sol_reserve = 0;
+        self.tokens_sold = 0;
+        self.graduation_reserve_target = DEFAULT_GRADUATION_RESERVE_TARGET;
+        self.buyback_bps = 0;
+        self.fee_split_enabled = false;
+        self.total_held = 0;
+        self.total_escrowed = 0;
+        self.pool_lp_mint = Pubkey::default();
+        self.legacy_supply = 0;
+    }
+
+    // Ends the curve once the real reserve reaches its target or the max supply is sold out
+    pub fn graduate_if_complete(&mut self) -> bool {
+        if self.is_bonding_curve_active
+            && (self.real_sol_reserve >= self.graduation_reserve_target || self.remaining_supply() == 0)
+        {
+            self.is_bonding_curve_active = false;
+            return true;
+        }
+        false
+    }
+
+    // Base units in one whole token
+    pub fn token_unit(&self) -> u64 {
+        10u64.pow(self.decimals as u32)
+    }
+
+    // Current curve price in lamports per whole token, rounded down
+    pub fn curve_price(&self) -> Result<u64> {
+        curve_market_cap(self.virtual_sol_reserve, self.virtual_token_reserve, self.token_unit())
+    }
+
+    // Lamports for `amount` base units at the fixed post-graduation price, rounded up
+    pub fn graduated_cost(&self, amount: u64) -> Result<u64> {
+        let cost = (amount as u128)
+            .checked_mul(GRADUATED_PRICE_PER_TOKEN as u128)
+            .ok_or(ErrorCode::CalculationError)?
+            .div_ceil(self.token_unit() as u128);
+        Ok(u64::try_from(cost).map_err(|_| ErrorCode::CalculationError)?)
+    }
+
+    // Base units that can still be minted before reaching the max supply
+    pub fn remaining_supply(&self) -> u64 {
+        if self.max_supply == 0 {
+            u64::MAX - self.total_supply
+        } else {
+            self.max_supply.saturating_sub(self.total_supply)
+        }
+    }
+
+    // The curve must hold more than the max supply, or it runs dry before the last tokens sell
+    pub fn check_curve_covers_max_supply(&self) -> Result<()> {
+        require!(
+            self.max_supply == 0 || self.max_supply < self.virtual_token_reserve,
+            ErrorCode::CurveReserveBelowMaxSupply
+        );
+        Ok(())
+    }
+
+    // Burns `amount` from a holder's balance and the supply; curve-bought tokens are burned last
+    pub fn burn_holder_tokens(&mut self, holder: &mut TokenHolder, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidBurnAmount);
+        require!(holder.balance >= amount, ErrorCode::InsufficientFunds);
+
+        let new_balance = holder.balance - amount;
+        self.track_holder_balance(holder.balance, new_balance)?;
+        holder.balance = new_balance;
+        holder.clamp_curve_balance();
+        self.burn_supply(amount)?;
+        self.check_supply_invariant()
+    }
+
+    // Removes burned base units from the supply; they can never be minted again
+    pub fn burn_supply(&mut self, amount: u64) -> Result<()> {
+        self.total_supply = self.total_supply.checked_sub(amount)
+            .ok_or(ErrorCode::CalculationError)?;
+        if self.max_supply > 0 {
+            self.max_supply = self.max_supply.checked_sub(amount)
+                .ok_or(ErrorCode::CalculationError)?;
+        }
+        Ok(())
+    }
+
+    // Adds newly minted base units to the supply, enforcing the max supply
+    pub fn add_supply(&mut self, amount: u64) -> Result<()> {
+        require!(amount <= self.remaining_supply(), ErrorCode::MaxSupplyExceeded);
+        self.total_supply += amount;
+        Ok(())
+    }
+
+    // Moves `amount` of the creator's existing balance into vesting escrow, up to MAX_VESTING_BPS of the supply
+    pub fn escrow_vesting(&mut self, creator: &mut TokenHolder, amount: u64) -> Result<()> {
+        require!(creator.balance >= amount, ErrorCode::InsufficientFunds);
+        
+        let reference_supply = if self.max_supply > 0 {
+            self.max_supply
+        } else {
+            self.total_supply
+        };
+        let total_escrowed = self.total_escrowed.checked_add(amount)
+            .ok_or(ErrorCode::CalculationError)?;
+        require!(
+            total_escrowed <= bps_of(reference_supply, MAX_VESTING_BPS)?,
+            ErrorCode::VestingCapExceeded
+        );
+        
+        let new_balance = creator.balance - amount;
+        self.track_holder_balance(creator.balance, new_balance)?;
+        creator.balance = new_balance;
+        creator.clamp_curve_balance();
+        self.total_escrowed = total_escrowed;
+        Ok(())
+    }
+
+    // Moves `amount` out of vesting escrow into the beneficiary's balance
+    pub fn release_escrow(&mut self, beneficiary: &mut TokenHolder, amount: u64) -> Result<()> {
+        self.total_escrowed = self.total_escrowed.checked_sub(amount)
+            .ok_or(ErrorCode::CalculationError)?;
+        let new_balance = beneficiary.balance.checked_add(amount)
+            .ok_or(ErrorCode::CalculationError)?;
+        self.track_holder_balance(beneficiary.balance, new_balance)?;
+        beneficiary.balance = new_balance;
+        Ok(())
+    }
+
+    // Moves the curve after `amount` tokens were bought for `sol_in` lamports
+    pub fn apply_curve_buy(&mut self, amount: u64, sol_in: u64) -> Result<()> {
+        self.virtual_sol_reserve = self.virtual_sol_reserve.checked_add(sol_in)
+            .ok_or(ErrorCode::CalculationError)?;
+        self.virtual_token_reserve = self.virtual_token_reserve.checked_sub(amount)
+            .ok_or(ErrorCode::InsufficientCurveLiquidity)?;
+        self.real_sol_reserve = self.real_sol_reserve.checked_add(sol_in)
+            .ok_or(ErrorCode::CalculationError)?;
+        self.tokens_sold = self.tokens_sold.checked_add(amount)
+            .ok_or(ErrorCode::CalculationError)?;
+        Ok(())
+    }
+
+    // Moves the curve after `amount` tokens were sold back for `sol_out` lamports
+    pub fn apply_curve_sell(&mut self, amount: u64, sol_out: u64) -> Result<()> {
+        require!(
+            amount <= self.tokens_sold && sol_out <= self.real_sol_reserve,
+            ErrorCode::InsufficientCurveLiquidity
+        );
+        self.virtual_sol_reserve = self.virtual_sol_reserve.checked_sub(sol_out)
+            .ok_or(ErrorCode::CalculationError)?;
+        self.virtual_token_reserve = self.virtual_token_reserve.checked_add(amount)
+            .ok_or(ErrorCode::CalculationError)?;
+        self.real_sol_reserve -= sol_out;
+        self.tokens_sold -= amount;
+        Ok(())
+    }
+
+    // Quotes a buy spending `amount_in` lamports, fees included, in the launch window state of `slot`.
+    // `platform_fee_bps` is the buyer's platform fee after any volume tier discount
+    pub fn quote_buy(&self, amount_in: u64, slot: u64, platform_fee_bps: u64) -> Result<TradeQuote> {
+        // Fees are charged on top of the price, so only part of the budget reaches the curve
+        let fee_bps = self.creator_fee.checked_add(platform_fee_bps)
+            .ok_or(ErrorCode::CalculationError)?;
+        let budget = ((amount_in as u128) * 10000 / (10000 + fee_bps as u128)) as u64;
+
+        // Buys are clipped to what is left of the max supply
+        let mut after = self.clone();
+        let (amount, price) = if self.is_bonding_curve_active {
+            let amount = curve_tokens_for(self.virtual_sol_reserve, self.virtual_token_reserve, budget)?
+                .min(self.remaining_supply());
+            let price = curve_buy_cost(self.virtual_sol_reserve, self.virtual_token_reserve, amount)?;
+            after.apply_curve_buy(amount, price)?;
+            (amount, price)
+        } else {
+            let amount = ((budget as u128) * (self.token_unit() as u128) / GRADUATED_PRICE_PER_TOKEN as u128)
+                .min(self.remaining_supply() as u128) as u64;
+            (amount, self.graduated_cost(amount)?)
+        };
+
+        let creator_fee = bps_of(price, self.creator_fee)?;
+        let platform_fee = bps_of(price, platform_fee_bps)?;
+        let sniper_tax_amount = if self.is_in_launch_window(slot) {
+            bps_of(amount, self.sniper_tax_bps)?
+        } else {
+            0
+        };
+        let (treasury_amount, holder_amount) = split_mint_amount(amount, sniper_tax_amount)?;
+        let post_trade_price = if self.is_bonding_curve_active {
+            after.curve_price()?
+        } else {
+            GRADUATED_PRICE_PER_TOKEN
+        };
+
+        Ok(TradeQuote {
+            amount_in: price + creator_fee + platform_fee,
+            amount_out: holder_amount,
+            creator_fee,
+            platform_fee,
+            treasury_amount,
+            price_impact_bps: self.price_impact_bps(&after)?,
+            post_trade_price,
+        })
+    }
+
+    // Quotes selling `tokens_in` back into the curve
+    pub fn quote_sell(&self, tokens_in: u64, platform_fee_bps: u64) -> Result<TradeQuote> {
+        require!(self.is_bonding_curve_active, ErrorCode::CurveNotActive);
+
+        let sol_out = curve_sell_proceeds(self.virtual_sol_reserve, self.virtual_token_reserve, tokens_in)?;
+        let mut after = self.clone();
+        after.apply_curve_sell(tokens_in, sol_out)?;
+
+        let creator_fee = bps_of(sol_out, self.creator_fee)?;
+        let platform_fee = bps_of(sol_out, platform_fee_bps)?;
+        let amount_out = sol_out
+            .checked_sub(creator_fee)
+            .and_then(|remaining| remaining.checked_sub(platform_fee))
+            .ok_or(ErrorCode::CalculationError)?;
+
+        Ok(TradeQuote {
+            amount_in: tokens_in,
+            amount_out,
+            creator_fee,
+            platform_fee,
+            treasury_amount: 0,
+            price_impact_bps: self.price_impact_bps(&after)?,
+            post_trade_price: after.curve_price()?,
+        })
+    }
+
+    // Quotes a holder-to-holder trade of `amount` tokens at `price`, which leaves the curve untouched
+    pub fn quote_trade(&self, amount: u64, price: u64, platform_fee_bps: u64) -> Result<TradeQuote> {
+        let trade_value = amount.checked_mul(price)
+            .ok_or(ErrorCode::CalculationError)?;
+        let creator_fee = bps_of(trade_value, self.creator_fee)?;
+        let platform_fee = bps_of(trade_value, platform_fee_bps)?;
+        let amount_out = trade_value
+            .checked_sub(creator_fee)
+            .and_then(|remaining| remaining.checked_sub(platform_fee))
+            .ok_or(ErrorCode::CalculationError)?;
+
+        Ok(TradeQuote {
+            amount_in: amount,
+            amount_out,
+            creator_fee,
+            platform_fee,
+            treasury_amount: 0,
+            price_impact_bps: 0,
+            post_trade_price: price,
+        })
+    }
+
+    // Relative move of the curve's spot price from this state to `after`, in basis points
+    pub fn price_impact_bps(&self, after: &TokenAccount) -> Result<u64> {
+        let before_value = (self.virtual_sol_reserve as u128) * (after.virtual_token_reserve as u128);
+        let after_value = (after.virtual_sol_reserve as u128) * (self.virtual_token_reserve as u128);
+        if before_value == 0 {
+            return Ok(0);
+        }
+        let impact = before_value.abs_diff(after_value)
+            .checked_mul(10000)
+            .ok_or(ErrorCode::CalculationError)?
+            / before_value;
+        Ok(u64::try_from(impact).unwrap_or(u64::MAX))
+    }
+
+    // Values the circulating supply at the current curve price
+    pub fn refresh_market_cap(&mut self) -> Result<()> {
+        self.market_cap = curve_market_cap(
+            self.virtual_sol_reserve,
+            self.virtual_token_reserve,
+            self.total_supply,
+        )?;
+        Ok(())
+    }
+
+    // Metaplex data mirrored from the token's name, symbol and URI
+    pub fn metaplex_data(&self) -> DataV2 {
+        DataV2 {
+            name: self.name.clone(),
+            symbol: self.symbol.clone(),
+            uri: self.metadata_uri.clone(),
+            seller_fee_basis_points: 0,
+            creators: None,
+            collection: None,
+            uses: None,
+        }
+    }
+
+    // Keeps `holder_count` and `total_held` in sync when a holder balance changes
+    pub fn track_holder_balance(&mut self, old_balance: u64, new_balance: u64) -> Result<()> {
+        self.total_held = self.total_held.checked_sub(old_balance)
+            .and_then(|total_held| total_held.checked_add(new_balance))
+            .ok_or(ErrorCode::CalculationError)?;
+        if old_balance == 0 && new_balance > 0 {
+            self.holder_count = self.holder_count.checked_add(1)
+                .ok_or(ErrorCode::CalculationError)?;
+        } else if old_balance > 0 && new_balance == 0 {
+            self.holder_count = self.holder_count.checked_sub(1)
+                .ok_or(ErrorCode::CalculationError)?;
+        }
+        Ok(())
+    }
+
+    // Moves a migrated v1 holder's balance out of the legacy supply into its holder PDA
+    pub fn credit_legacy_balance(&mut self, holder: &mut TokenHolder, amount: u64) -> Result<()> {
+        self.legacy_supply = self.legacy_supply.checked_sub(amount)
+            .ok_or(ErrorCode::SupplyInvariantViolated)?;
+        let new_balance = holder.balance.checked_add(amount)
+            .ok_or(ErrorCode::CalculationError)?;
+        self.track_holder_balance(holder.balance, new_balance)?;
+        holder.balance = new_balance;
+        Ok(())
+    }
+
+    // Checks that the supply equals the sum of all holder balances, tokens held in escrow and unmigrated v1 supply
+    pub fn check_supply_invariant(&self) -> Result<()> {
+        let accounted = self.total_held as u128 + self.total_escrowed as u128 + self.legacy_supply as u128;
+        require!(accounted == self.total_supply as u128, ErrorCode::SupplyInvariantViolated);
+        Ok(())
+    }
+
+    // Checks a transfer of `amount` that leaves the receiver with `new_balance`
+    pub fn check_holding_limits(&self, amount: u64, new_balance: u64) -> Result<()> {
+        if self.relax_limits_after_graduation && !self.is_bonding_curve_active {
+            return Ok(());
+        }
+
+        // Limits are shares of the max supply, or of the reference supply set with them for uncapped tokens
+        let reference_supply = if self.max_supply > 0 {
+            self.max_supply
+        } else {
+            self.limit_reference_supply
+        };
+
+        if self.max_tx_bps > 0 {
+            let max_tx = bps_of(reference_supply, self.max_tx_bps)?;
+            require!(amount <= max_tx, ErrorCode::MaxTransactionExceeded);
+        }
+
+        if self.max_wallet_bps > 0 {
+            let max_wallet = bps_of(reference_supply, self.max_wallet_bps)?;
+            require!(new_balance <= max_wallet, ErrorCode::MaxWalletExceeded);
+        }
+
+        Ok(())
+    }
+}
+
+// Validates the name, symbol, metadata URI and fees of a new token
+pub fn validate_token_params(
+    name: &str,
+    symbol: &str,
+    metadata_uri: &str,
+    creator_fee: u64,
+    platform_fee: u64,
+) -> Result<()> {
+    require!(!name.is_empty(), ErrorCode::InvalidTokenName);
+    require!(!symbol.is_empty(), ErrorCode::InvalidTokenSymbol);
+    require!(symbol.len() <= 8, ErrorCode::TokenSymbolTooLong);
+    require!(name.len() <= 32, ErrorCode::TokenNameTooLong);
+    validate_metadata_uri(metadata_uri)?;
+
+    // Validate fees
+    require!(
+        creator_fee.saturating_add(platform_fee) <= 1000, // Maximum 10% total fees (1000 basis points)
+        ErrorCode::InvalidFees
+    );
+
+    Ok(())
+}
+
+// Validates the decimals and max supply of a new token
+pub fn validate_supply_params(decimals: u8, max_supply: u64) -> Result<()> {
+    require!(decimals <= MAX_TOKEN_DECIMALS, ErrorCode::InvalidSupplyConfig);
+    require!(max_supply > 0, ErrorCode::InvalidSupplyConfig);
+    Ok(())
+}
+
+// Default virtual token reserve in base units for a mint with `decimals`
+pub fn default_virtual_token_reserve(decimals: u8) -> u64 {
+    DEFAULT_VIRTUAL_TOKEN_RESERVE * 10u64.pow(decimals as u32)
+}
+
+// Reads a pre-versioning account, checking its discriminator and allocated size
+pub fn deserialize_v1<T: AnchorDeserialize>(
+    data: &[u8],
+    discriminator: &[u8; 8],
+    v1_len: usize,
+) -> Result<T> {
+    require!(
+        data.len() >= 8 && &data[..8] == discriminator,
+        ErrorCode::InvalidAccountVersion
+    );
+    require!(data.len() == 8 + v1_len, ErrorCode::AccountAlreadyMigrated);
+
+    T::deserialize(&mut &data[8..]).map_err(|_| error!(ErrorCode::InvalidAccountVersion))
+}
+
+// Grows an account to `new_len`, topping up rent from `payer`, and writes `account`
+pub fn realloc_and_write<'info, T: AccountSerialize>(
+    account_info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    new_len: usize,
+    account: &T,
+) -> Result<()> {
+    let required = Rent::get()?.minimum_balance(new_len);
+    if required > account_info.lamports() {
+        invoke(
+            &system_instruction::transfer(payer.key, account_info.key, required - account_info.lamports()),
+            &[payer.clone(), account_info.clone(), system_program.clone()],
+        )?;
+    }
+
+    account_info.realloc(new_len, true)?;
+    let mut data = account_info.try_borrow_mut_data()?;
+    account.try_serialize(&mut &mut data[..])?;
+
+    Ok(())
+}
+
+// Validates a metadata URI's length, scheme and characters
+pub fn validate_metadata_uri(uri: &str) -> Result<()> {
+    require!(!uri.is_empty(), ErrorCode::InvalidMetadataUri);
+    require!(uri.len() <= 200, ErrorCode::MetadataUriTooLong);
+
+    let path = ALLOWED_METADATA_URI_SCHEMES
+        .iter()
+        .find_map(|scheme| uri.strip_prefix(scheme))
+        .ok_or(ErrorCode::UnsupportedMetadataUriScheme)?;
+    require!(!path.is_empty(), ErrorCode::InvalidMetadataUri);
+    require!(
+        !uri.chars().any(|c| c.is_control() || c.is_whitespace()),
+        ErrorCode::InvalidMetadataUriCharacters
+    );
+
+    Ok(())
+}
+
+// Computes `bps` basis points of `value`
+pub fn bps_of(value: u64, bps: u64) -> Result<u64> {
+    let result = (value as u128)
+        .checked_mul(bps as u128)
+        .ok_or(ErrorCode::CalculationError)?
+        / 10000;
+    Ok(result as u64)
+}
+
+// Platform fee in basis points a trader pays on `token_account`, discounted by the tier their trailing
+// 30-day volume reaches. Without a platform config or trader stats the token's own rate applies
+pub fn tiered_platform_fee(
+    token_account: &TokenAccount,
+    platform_config: Option<&PlatformConfig>,
+    trader_stats: Option<&TraderStats>,
+    now: i64,
+) -> Result<(Option<u8>, u64)> {
+    let (Some(platform_config), Some(trader_stats)) = (platform_config, trader_stats) else {
+        return Ok((None, token_account.platform_fee));
+    };
+    match platform_config.fee_tier_for(trader_stats.rolling_volume(now)) {
+        Some((tier, fee_tier)) => {
+            let discount = bps_of(token_account.platform_fee, fee_tier.discount_bps)?;
+            Ok((Some(tier), token_account.platform_fee - discount))
+        }
+        None => Ok((None, token_account.platform_fee)),
+    }
+}
+
+// Validates a fee tier table: at most MAX_FEE_TIERS tiers with rising volume thresholds and
+// discounts that never shrink as volume grows
+pub fn validate_fee_tiers(fee_tiers: &[FeeTier]) -> Result<()> {
+    require!(fee_tiers.len() <= MAX_FEE_TIERS, ErrorCode::InvalidFeeTiers);
+    require!(
+        fee_tiers.iter().all(|tier| tier.discount_bps <= 10000),
+        ErrorCode::InvalidFeeTiers
+    );
+    require!(
+        fee_tiers.windows(2).all(|pair| {
+            pair[0].min_volume < pair[1].min_volume && pair[0].discount_bps <= pair[1].discount_bps
+        }),
+        ErrorCode::InvalidFeeTiers
+    );
+    Ok(())
+}
+
+// Validates a creator fee split: 1 to MAX_FEE_SPLIT_RECIPIENTS distinct wallets with non-zero
+// weights summing to 10000 basis points
+pub fn validate_fee_split(shares: &[FeeShare]) -> Result<()> {
+    require!(
+        !shares.is_empty() && shares.len() <= MAX_FEE_SPLIT_RECIPIENTS,
+        ErrorCode::InvalidFeeSplit
+    );
+    require!(shares.iter().all(|share| share.weight_bps > 0), ErrorCode::InvalidFeeSplit);
+    let total_weight = shares.iter().try_fold(0u64, |total, share| total.checked_add(share.weight_bps));
+    require!(total_weight == Some(10000), ErrorCode::InvalidFeeSplit);
+    require!(
+        shares.iter().enumerate().all(|(index, share)| {
+            shares[..index].iter().all(|earlier| earlier.wallet != share.wallet)
+        }),
+        ErrorCode::InvalidFeeSplit
+    );
+    Ok(())
+}
+
+// Splits a mint of `amount` into the treasury share (1% plus sniper tax) and the holder share.
+// The 1% rounds down, so mints below 100 base units carry no treasury share.
+pub fn split_mint_amount(amount: u64, sniper_tax_amount: u64) -> Result<(u64, u64)> {
+    let treasury_amount = (amount / 100).checked_add(sniper_tax_amount)
+        .ok_or(ErrorCode::CalculationError)?;
+    let holder_amount = amount.checked_sub(treasury_amount)
+        .ok_or(ErrorCode::CalculationError)?;
+    Ok((treasury_amount, holder_amount))
+}
+
+// Lamports needed to buy `amount` tokens from a constant-product curve, rounded up
+pub fn curve_buy_cost(virtual_sol: u64, virtual_token: u64, amount: u64) -> Result<u64> {
+    require!(amount < virtual_token, ErrorCode::InsufficientCurveLiquidity);
+    let numerator = (virtual_sol as u128)
+        .checked_mul(amount as u128)
+        .ok_or(ErrorCode::CalculationError)?;
+    let denominator = (virtual_token - amount) as u128;
+    let cost = numerator.div_ceil(denominator);
+    Ok(u64::try_from(cost).map_err(|_| ErrorCode::CalculationError)?)
+}
+
+// Tokens a constant-product curve gives for `sol_in` lamports, rounded down
+pub fn curve_tokens_for(virtual_sol: u64, virtual_token: u64, sol_in: u64) -> Result<u64> {
+    let numerator = (virtual_token as u128)
+        .checked_mul(sol_in as u128)
+        .ok_or(ErrorCode::CalculationError)?;
+    let denominator = (virtual_sol as u128)
+        .checked_add(sol_in as u128)
+        .ok_or(ErrorCode::CalculationError)?;
+    require!(denominator > 0, ErrorCode::CalculationError);
+    Ok((numerator / denominator) as u64)
+}
+
+// Lamports paid out for selling `amount` tokens into a constant-product curve, rounded down
+pub fn curve_sell_proceeds(virtual_sol: u64, virtual_token: u64, amount: u64) -> Result<u64> {
+    let numerator = (virtual_sol as u128)
+        .checked_mul(amount as u128)
+        .ok_or(ErrorCode::CalculationError)?;
+    let denominator = (virtual_token as u128)
+        .checked_add(amount as u128)
+        .ok_or(ErrorCode::CalculationError)?;
+    Ok((numerator / denominator) as u64)
+}
+
+// Values `supply` tokens at the curve's spot price
+pub fn curve_market_cap(virtual_sol: u64, virtual_token: u64, supply: u64) -> Result<u64> {
+    require!(virtual_token > 0, ErrorCode::CalculationError);
+    let market_cap = (virtual_sol as u128)
+        .checked_mul(supply as u128)
+        .ok_or(ErrorCode::CalculationError)?
+        / virtual_token as u128;
+    Ok(u64::try_from(market_cap).map_err(|_| ErrorCode::CalculationError)?)
+}
+
+// Moves `amount` lamports out of a system-owned account, skipping empty transfers
+pub fn transfer_lamports<'info>(
+    from: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    if amount > 0 {
+        invoke(
+            &system_instruction::transfer(from.key, to.key, amount),
+            &[from.clone(), to.clone(), system_program.clone()],
+        )?;
+    }
+    Ok(())
+}
+
+// Pays a fee of `amount` lamports from `payer` into `vault`
+pub fn deposit_fee<'info>(
+    payer: &AccountInfo<'info>,
+    vault: &mut Account<'info, FeeVault>,
+    system_program: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    transfer_lamports(payer, &vault.to_account_info(), system_program, amount)?;
+    vault.record_deposit(amount)
+}
+
+// Mints `amount` base units to `to`, signed by the token's mint authority PDA, skipping empty mints
+pub fn mint_to_holder<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    mint_authority: &AccountInfo<'info>,
+    token_account: Pubkey,
+    mint_authority_bump: u8,
+    to: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    if amount > 0 {
+        let mint_authority_seeds: &[&[u8]] = &[
+            b"mint_authority",
+            token_account.as_ref(),
+            &[mint_authority_bump],
+        ];
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                MintTo {
+                    mint: mint.clone(),
+                    to: to.clone(),
+                    authority: mint_authority.clone(),
+                },
+                &[mint_authority_seeds],
+            ),
+            amount,
+        )?;
+    }
+    Ok(())
+}
+
+// Transfer fee a Token-2022 mint withholds from a transfer of `amount` in the current epoch
+pub fn epoch_transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    let fee = state.get_extension::<transfer_fee::TransferFeeConfig>()?
+        .calculate_epoch_fee(Clock::get()?.epoch, amount)
+        .ok_or(ErrorCode::CalculationError)?;
+    Ok(fee)
+}
+
+// Burns `amount` base units from `from`, signed by its owner
+pub fn burn_from_holder<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    from: &AccountInfo<'info>,
+    owner: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    token_interface::burn(
+        CpiContext::new(
+            token_program.clone(),
+            Burn {
+                mint: mint.clone(),
+                from: from.clone(),
+                authority: owner.clone(),
+            },
+        ),
+        amount,
+    )
+}
+
+// Moves `amount` lamports out of an account owned by this program
+pub fn move_lamports<'info>(
+    from: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    **from.try_borrow_mut_lamports()? = from.lamports().checked_sub(amount)
+        .ok_or(ErrorCode::CalculationError)?;
+    **to.try_borrow_mut_lamports()? = to.lamports().checked_add(amount)
+        .ok_or(ErrorCode::CalculationError)?;
+    Ok(())
+}
+
+// Closes an account owned by this program, sending all of its lamports to `destination`
+pub fn close_program_account<'info>(
+    account: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+) -> Result<()> {
+    move_lamports(account, destination, account.lamports())?;
+    account.assign(&System::id());
+    account.realloc(0, false)?;
+    Ok(())
+}
+
+// Sends every unclaimed lamport in `vault` to `recipient`, leaving its rent in place
+pub fn withdraw_fees<'info>(
+    vault: &mut Account<'info, FeeVault>,
+    recipient: &AccountInfo<'info>,
+) -> Result<u64> {
+    let amount = vault.claimable()?;
+    require!(amount > 0, ErrorCode::NoFeesToClaim);
+
+    vault.total_claimed = vault.total_claimed.checked_add(amount)
+        .ok_or(ErrorCode::CalculationError)?;
+    move_lamports(&vault.to_account_info(), recipient, amount)?;
+
+    Ok(amount)
+}
+
+// Verifies a sorted-pair keccak merkle proof for `leaf` against `root`
+pub fn verify_merkle_proof(proof: &[[u8; 32]], root: [u8; 32], leaf: [u8; 32]) -> bool {
+    let mut computed = leaf;
+    for node in proof {
+        computed = if computed <= *node {
+            keccak::hashv(&[&computed, node]).0
+        } else {
+            keccak::hashv(&[node, &computed]).0
+        };
+    }
+    computed == root
 }
 
 #[account]
+#[derive(InitSpace)]
 pub struct TokenHolder {
+    pub version: u8,         // 1 byte - account layout version
     pub owner: Pubkey,       // 32 bytes
+    pub token_account: Pubkey, // 32 bytes - token this record holds a balance of
     pub balance: u64,        // 8 bytes
     pub last_trade: i64,     // 8 bytes - timestamp of last trade
     pub initial_entry: i64,  // 8 bytes - timestamp of first token acquisition
     pub is_verified: bool,   // 1 byte - if holder is KYC verified
+    pub verification_expiry: i64, // 8 bytes - timestamp when KYC verification lapses
+    pub launch_window_bought: u64, // 8 bytes - tokens bought during the anti-sniper window
+    pub curve_balance: u64,  // 8 bytes - part of the balance bought from the curve, the only part it buys back
+    pub bump: u8,            // 1 byte - bump of the ["holder", token_account, owner] PDA
+    pub reserved: [u8; 32],  // 32 bytes - padding for future fields without a realloc
+}
+
+impl TokenHolder {
+    // Upgrades a v1 holder, which had no version byte, to the current layout at its PDA for `token_account`
+    pub fn from_v1(v1: TokenHolderV1, token_account: Pubkey, bump: u8) -> Self {
+        Self {
+            version: CURRENT_ACCOUNT_VERSION,
+            owner: v1.owner,
+            token_account,
+            balance: v1.balance,
+            last_trade: v1.last_trade,
+            initial_entry: v1.initial_entry,
+            is_verified: v1.is_verified,
+            verification_expiry: 0,
+            launch_window_bought: 0,
+            curve_balance: 0,
+            bump,
+            reserved: [0u8; 32],
+        }
+    }
+
+    pub fn is_verification_active(&self, now: i64) -> bool {
+        self.is_verified && self.verification_expiry > now
+    }
+
+    pub fn check_trade_cooldown(&self, now: i64, cooldown_seconds: i64) -> Result<()> {
+        if cooldown_seconds > 0 && self.last_trade > 0 {
+            let next_trade = self.last_trade.checked_add(cooldown_seconds)
+                .ok_or(ErrorCode::CalculationError)?;
+            require!(now >= next_trade, ErrorCode::TradeCooldownActive);
+        }
+        Ok(())
+    }
+
+    // Binds a freshly created holder PDA to its token and wallet; existing holders are left untouched
+    pub fn init_if_new(&mut self, token_account: Pubkey, owner: Pubkey, bump: u8) {
+        if self.version == 0 {
+            self.version = CURRENT_ACCOUNT_VERSION;
+            self.token_account = token_account;
+            self.owner = owner;
+            self.bump = bump;
+        }
+    }
+
+    // Takes sold tokens out of the curve balance; presale, vested and treasury tokens never enter it,
+    // so they cannot be sold into a reserve they did not pay into
+    pub fn debit_curve_balance(&mut self, amount: u64) -> Result<()> {
+        self.curve_balance = self.curve_balance.checked_sub(amount)
+            .ok_or(ErrorCode::ExceedsCurveBalance)?;
+        Ok(())
+    }
+
+    // Keeps the curve balance within the balance after tokens leave it other than by a curve sell
+    pub fn clamp_curve_balance(&mut self) {
+        self.curve_balance = self.curve_balance.min(self.balance);
+    }
+
+    // Moves up to `amount` of the curve balance to `buyer` along with a transfer of `amount` tokens
+    pub fn transfer_curve_balance(&mut self, buyer: &mut TokenHolder, amount: u64) -> Result<()> {
+        let curve_amount = amount.min(self.curve_balance);
+        self.curve_balance -= curve_amount;
+        buyer.curve_balance = buyer.curve_balance.checked_add(curve_amount)
+            .ok_or(ErrorCode::CalculationError)?;
+        Ok(())
+    }
+
+    pub fn record_trade(&mut self, now: i64) {
+        if self.initial_entry == 0 {
+            self.initial_entry = now;
+        }
+        self.last_trade = now;
+    }
+}
+
+// Token account layout before versioning, kept to migrate deployed accounts
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct TokenAccountV1 {
+    pub name: String,
+    pub symbol: String,
+    pub creator_fee: u64,
+    pub platform_fee: u64,
+    pub authority: Pubkey,
+    pub treasury: Pubkey,
+    pub total_supply: u64,
+    pub is_frozen: bool,
+    pub is_bonding_curve_active: bool,
+    pub bonding_curve_cap: u64,
+    pub market_cap: u64,
+    pub metadata_uri: String,
+    pub last_updated_at: i64,
+    pub creation_time: i64,
+    pub verified: bool,
+}
+
+impl TokenAccountV1 {
+    // Space v1 accounts were allocated with, excluding the discriminator. It leaves out
+    // the 4-byte String length prefixes, so it only identifies v1 accounts by size
+    pub const LEN: usize = 32 + 8 + 8 + 8 + 32 + 32 + 8 + 1 + 1 + 8 + 8 + 200 + 8 + 8 + 1;
+}
+
+// Token holder layout before versioning, kept to migrate deployed accounts
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct TokenHolderV1 {
+    pub owner: Pubkey,
+    pub balance: u64,
+    pub last_trade: i64,
+    pub initial_entry: i64,
+    pub is_verified: bool,
+}
+
+impl TokenHolderV1 {
+    // Space v1 accounts were allocated with, excluding the discriminator
+    pub const LEN: usize = 32 + 8 + 8 + 8 + 1;
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct LaunchRecord {
+    pub token_account: Pubkey,      // 32 bytes
+    pub launch_date: i64,           // 8 bytes
+    pub launch_params: LaunchParams, // 81 bytes
+    pub is_verified: bool,          // 1 byte
+    pub initial_market_cap: u64,    // 8 bytes
+    pub initial_holder_count: u16,  // 2 bytes
+    pub creator_wallet: Pubkey,     // 32 bytes
+    pub presale_tokens_sold: u64,   // 8 bytes
+    pub presale_proceeds: u64,      // 8 bytes - in lamports
+    pub verified_by: Pubkey,        // 32 bytes - registered verifier that verified the launch
+    pub bump: u8,                   // 1 byte
+}
+
+// Bonding curve shapes a token can launch with
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum CurveType {
+    Quadratic,                      // price = (supply / 10000)^2 + 0.01, the original launch curve
+    ConstantProduct,                // virtual-reserve curve, as used by mint_tokens and sell_tokens
+}
+
+// Typed launch parameters stored in the launch record for indexers
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct LaunchParams {
+    pub initial_price: u64,         // 8 bytes - in lamports per token
+    pub curve_type: CurveType,      // 1 byte
+    pub dev_buy_amount: u64,        // 8 bytes - tokens bought by the creator at launch
+    pub socials_hash: [u8; 32],     // 32 bytes - hash of the creator's social links
+    pub metadata_hash: [u8; 32],    // 32 bytes - hash of the off-chain metadata JSON
+}
+
+// New struct for managing token trading parameters
+#[account]
+#[derive(InitSpace)]
+pub struct TradingConfig {
+    pub token_account: Pubkey,      // 32 bytes
+    pub min_trade_amount: u64,      // 8 bytes
+    pub max_slippage: u16,          // 2 bytes - in basis points (100 = 1%)
+    pub trading_enabled: bool,      // 1 byte
+    pub authority: Pubkey,          // 32 bytes
+}
+
+// Platform-wide settings owned by the platform authority
+#[account]
+#[derive(InitSpace)]
+pub struct PlatformConfig {
+    pub authority: Pubkey,          // 32 bytes
+    #[max_len(MAX_FEE_TIERS)]
+    pub fee_tiers: Vec<FeeTier>,    // 4 + 16 * MAX_FEE_TIERS bytes - sorted by min_volume
+    pub bump: u8,                   // 1 byte
+}
+
+impl PlatformConfig {
+    // Highest tier, and its index, reached by a trailing 30-day `volume`
+    pub fn fee_tier_for(&self, volume: u64) -> Option<(u8, &FeeTier)> {
+        self.fee_tiers
+            .iter()
+            .enumerate()
+            .rev()
+            .find(|(_, tier)| volume >= tier.min_volume)
+            .map(|(index, tier)| (index as u8, tier))
+    }
+}
+
+// Platform fee discount for traders whose trailing 30-day volume reaches `min_volume`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct FeeTier {
+    pub min_volume: u64,            // 8 bytes - lamports traded in the window
+    pub discount_bps: u64,          // 8 bytes - share of the token's platform fee waived
+}
+
+// Per-wallet trading volume, bucketed by day, that platform fee tiers are keyed on
+#[account]
+#[derive(InitSpace)]
+pub struct TraderStats {
+    pub wallet: Pubkey,             // 32 bytes
+    pub daily_volume: [u64; FEE_TIER_WINDOW_DAYS], // 240 bytes - lamports traded per day, indexed by day % 30
+    pub last_trade_day: i64,        // 8 bytes - day (unix_timestamp / 86400) of the latest recorded trade
+    pub total_volume: u64,          // 8 bytes - lifetime volume in lamports
+    pub bump: u8,                   // 1 byte
+}
+
+impl TraderStats {
+    // Volume traded in the FEE_TIER_WINDOW_DAYS days ending on the day of `now`
+    pub fn rolling_volume(&self, now: i64) -> u64 {
+        let window = FEE_TIER_WINDOW_DAYS as i64;
+        let elapsed = (now.div_euclid(SECONDS_PER_DAY) - self.last_trade_day).clamp(0, window);
+        (0..window - elapsed)
+            .map(|age| self.daily_volume[(self.last_trade_day - age).rem_euclid(window) as usize])
+            .fold(0, u64::saturating_add)
+    }
+
+    // Adds `amount` lamports to the bucket of the day of `now`, clearing days that left the window
+    pub fn record_volume(&mut self, now: i64, amount: u64) -> Result<()> {
+        let window = FEE_TIER_WINDOW_DAYS as i64;
+        let today = now.div_euclid(SECONDS_PER_DAY);
+        let elapsed = (today - self.last_trade_day).clamp(0, window);
+        for day in (today - elapsed + 1)..=today {
+            self.daily_volume[day.rem_euclid(window) as usize] = 0;
+        }
+        self.last_trade_day = self.last_trade_day.max(today);
+
+        let bucket = &mut self.daily_volume[self.last_trade_day.rem_euclid(window) as usize];
+        *bucket = bucket.checked_add(amount)
+            .ok_or(ErrorCode::CalculationError)?;
+        self.total_volume = self.total_volume.checked_add(amount)
+            .ok_or(ErrorCode::CalculationError)?;
+        Ok(())
+    }
+}
+
+// Wallet registered by the platform to attest holder KYC status
+#[account]
+#[derive(InitSpace)]
+pub struct Attester {
+    pub attester: Pubkey,           // 32 bytes
+    pub is_active: bool,            // 1 byte
+    pub registered_at: i64,         // 8 bytes
+    pub bump: u8,                   // 1 byte
+}
+
+// Wallet registered by the platform to verify launch records
+#[account]
+#[derive(InitSpace)]
+pub struct Verifier {
+    pub verifier: Pubkey,           // 32 bytes
+    pub is_active: bool,            // 1 byte
+    pub registered_at: i64,         // 8 bytes
+    pub bump: u8,                   // 1 byte
+}
+
+// Allowlisted presale that runs before the public bonding curve
+#[account]
+#[derive(InitSpace)]
+pub struct PresaleConfig {
+    pub token_account: Pubkey,      // 32 bytes
+    pub merkle_root: [u8; 32],      // 32 bytes - root of keccak(wallet) leaves
+    pub max_per_wallet: u64,        // 8 bytes
+    pub price_per_token: u64,       // 8 bytes - in lamports
+    pub start_time: i64,            // 8 bytes
+    pub end_time: i64,              // 8 bytes
+    pub tokens_sold: u64,           // 8 bytes
+    pub proceeds: u64,              // 8 bytes - in lamports
+    pub bump: u8,                   // 1 byte
+}
+
+// Per-wallet presale purchase tracking
+#[account]
+#[derive(InitSpace)]
+pub struct PresaleReceipt {
+    pub presale: Pubkey,            // 32 bytes
+    pub buyer: Pubkey,              // 32 bytes
+    pub amount_purchased: u64,      // 8 bytes
+    pub bump: u8,                   // 1 byte
+}
+
+// Escrowed allocation that unlocks linearly after a cliff
+#[account]
+#[derive(InitSpace)]
+pub struct VestingSchedule {
+    pub token_account: Pubkey,      // 32 bytes
+    pub beneficiary: Pubkey,        // 32 bytes
+    pub total_amount: u64,          // 8 bytes
+    pub released_amount: u64,       // 8 bytes
+    pub start_time: i64,            // 8 bytes
+    pub cliff_time: i64,            // 8 bytes - nothing unlocks before this
+    pub end_time: i64,              // 8 bytes - everything is unlocked at this time
+    pub bump: u8,                   // 1 byte
+}
+
+impl VestingSchedule {
+    // Total amount unlocked at `now`, including what was already released
+    pub fn vested_amount(&self, now: i64) -> Result<u64> {
+        if now < self.cliff_time {
+            return Ok(0);
+        }
+        if now >= self.end_time {
+            return Ok(self.total_amount);
+        }
+
+        let elapsed = (now - self.start_time) as u128;
+        let duration = (self.end_time - self.start_time) as u128;
+        let vested = (self.total_amount as u128)
+            .checked_mul(elapsed)
+            .ok_or(ErrorCode::CalculationError)?
+            / duration;
+        Ok(vested as u64)
+    }
+
+    pub fn locked_amount(&self) -> u64 {
+        self.total_amount.saturating_sub(self.released_amount)
+    }
+}
+
+// LP tokens escrowed after graduation until the unlock time
+#[account]
+#[derive(InitSpace)]
+pub struct LiquidityLock {
+    pub token_account: Pubkey,      // 32 bytes
+    pub lp_mint: Pubkey,            // 32 bytes
+    pub owner: Pubkey,              // 32 bytes - receives the LP tokens on unlock
+    pub lp_vault: Pubkey,           // 32 bytes - program-owned LP token account
+    pub amount: u64,                // 8 bytes
+    pub locked_at: i64,             // 8 bytes
+    pub unlock_time: i64,           // 8 bytes
+    pub bump: u8,                   // 1 byte
+}
+
+// Partner wallet earning a share of the platform fee on the buys and sells it refers
+#[account]
+#[derive(InitSpace)]
+pub struct Referrer {
+    pub wallet: Pubkey,             // 32 bytes - receives claimed earnings
+    pub share_bps: u64,             // 8 bytes - share of the platform fee in basis points
+    pub total_earned: u64,          // 8 bytes - lamports earned, held by this account until claimed
+    pub total_claimed: u64,         // 8 bytes
+    pub is_active: bool,            // 1 byte
+    pub registered_at: i64,         // 8 bytes
+    pub bump: u8,                   // 1 byte
+}
+
+impl Referrer {
+    // Lamports earned but not yet claimed
+    pub fn claimable(&self) -> Result<u64> {
+        Ok(self.total_earned.checked_sub(self.total_claimed)
+            .ok_or(ErrorCode::CalculationError)?)
+    }
+
+    // Records a referral fee of `amount` lamports paid into this account
+    pub fn record_earnings(&mut self, amount: u64) -> Result<()> {
+        self.total_earned = self.total_earned.checked_add(amount)
+            .ok_or(ErrorCode::CalculationError)?;
+        Ok(())
+    }
+}
+
+// Result of the quote instructions, returned as return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TradeQuote {
+    pub amount_in: u64,             // Lamports spent on buys (fees included), tokens in for sells and trades
+    pub amount_out: u64,            // Tokens credited to the buyer, or lamports paid to the seller after fees
+    pub creator_fee: u64,           // Creator fee in lamports
+    pub platform_fee: u64,          // Platform fee in lamports
+    pub treasury_amount: u64,       // Tokens kept by the treasury on buys (1% plus any sniper tax)
+    pub price_impact_bps: u64,      // Move of the spot price caused by the trade
+    pub post_trade_price: u64,      // Spot price after the trade in lamports per token
+}
+
+// Program-owned vault holding fee lamports until they are claimed
+#[account]
+#[derive(InitSpace)]
+pub struct FeeVault {
+    pub token_account: Pubkey,      // 32 bytes - default for the platform-wide vault
+    pub total_collected: u64,       // 8 bytes - lamports paid in as fees
+    pub total_claimed: u64,         // 8 bytes - lamports paid out to the recipient
+    pub buyback_reserved: u64,      // 8 bytes - lamports committed to buybacks but not yet spent
+    pub total_buyback_spent: u64,   // 8 bytes - lamports spent on buyback-and-burn
+    pub last_buyback_at: i64,       // 8 bytes
+    pub bump: u8,                   // 1 byte
+}
+
+impl FeeVault {
+    // Lamports collected but not yet claimed or committed to buybacks
+    pub fn claimable(&self) -> Result<u64> {
+        Ok(self.total_collected
+            .checked_sub(self.total_claimed)
+            .and_then(|remaining| remaining.checked_sub(self.total_buyback_spent))
+            .and_then(|remaining| remaining.checked_sub(self.buyback_reserved))
+            .ok_or(ErrorCode::CalculationError)?)
+    }
+
+    // Sets aside `amount` of the collected lamports for buyback-and-burn
+    pub fn commit_buyback(&mut self, amount: u64) -> Result<()> {
+        self.buyback_reserved = self.buyback_reserved.checked_add(amount)
+            .ok_or(ErrorCode::CalculationError)?;
+        Ok(())
+    }
+
+    // Records `amount` committed lamports spent by a buyback
+    pub fn spend_buyback(&mut self, amount: u64) -> Result<()> {
+        self.buyback_reserved = self.buyback_reserved.checked_sub(amount)
+            .ok_or(ErrorCode::CalculationError)?;
+        self.total_buyback_spent = self.total_buyback_spent.checked_add(amount)
+            .ok_or(ErrorCode::CalculationError)?;
+        Ok(())
+    }
+
+    // Lamports released for claiming over the vault's lifetime, whether claimed yet or not
+    pub fn released(&self) -> Result<u64> {
+        Ok(self.claimable()?.checked_add(self.total_claimed)
+            .ok_or(ErrorCode::CalculationError)?)
+    }
+
+    // Records a fee of `amount` lamports paid into the vault
+    pub fn record_deposit(&mut self, amount: u64) -> Result<()> {
+        self.total_collected = self.total_collected.checked_add(amount)
+            .ok_or(ErrorCode::CalculationError)?;
+        Ok(())
+    }
+}
+
+// Weighted recipient of a creator fee split, as passed to set_fee_split
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct FeeShare {
+    pub wallet: Pubkey,             // 32 bytes
+    pub weight_bps: u64,            // 8 bytes - share of the creator fees in basis points
+}
+
+// Recipient of a creator fee split with its allocated but unclaimed fees
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct FeeSplitRecipient {
+    pub wallet: Pubkey,             // 32 bytes
+    pub weight_bps: u64,            // 8 bytes - 0 once removed from the split with fees still owed
+    pub owed: u64,                  // 8 bytes - lamports allocated and not yet claimed
+    pub total_claimed: u64,         // 8 bytes
+}
+
+// Per-token split of the creator fees among several wallets, each claiming separately from the creator fee vault
+#[account]
+#[derive(InitSpace)]
+pub struct FeeSplit {
+    pub token_account: Pubkey,      // 32 bytes
+    #[max_len(MAX_FEE_SPLIT_RECIPIENTS)]
+    pub recipients: Vec<FeeSplitRecipient>, // 4 + 56 * MAX_FEE_SPLIT_RECIPIENTS bytes
+    pub distributed: u64,           // 8 bytes - lamports released by the vault and allocated to recipients
+    #[max_len(MAX_FEE_SPLIT_RECIPIENTS)]
+    pub pending_shares: Vec<FeeShare>, // 4 + 40 * MAX_FEE_SPLIT_RECIPIENTS bytes - empty when no change is proposed
+    pub pending_effective_at: i64,  // 8 bytes
+    pub bump: u8,                   // 1 byte
+}
+
+impl FeeSplit {
+    // Allocates the lamports the vault released since the last allocation by weight.
+    // Rounding remainders stay unallocated and roll into the next allocation
+    pub fn allocate(&mut self, released: u64) -> Result<()> {
+        let amount = released.checked_sub(self.distributed)
+            .ok_or(ErrorCode::CalculationError)?;
+        for recipient in self.recipients.iter_mut() {
+            let share = bps_of(amount, recipient.weight_bps)?;
+            recipient.owed = recipient.owed.checked_add(share)
+                .ok_or(ErrorCode::CalculationError)?;
+            self.distributed = self.distributed.checked_add(share)
+                .ok_or(ErrorCode::CalculationError)?;
+        }
+        Ok(())
+    }
+
+    // Replaces the weights with `shares`. Wallets that stay keep their balances, and removed wallets
+    // with fees still owed stay on with no weight until they claim
+    pub fn apply_shares(&mut self, shares: &[FeeShare]) -> Result<()> {
+        let mut recipients: Vec<FeeSplitRecipient> = shares
+            .iter()
+            .map(|share| {
+                let existing = self.recipients.iter().find(|recipient| recipient.wallet == share.wallet);
+                FeeSplitRecipient {
+                    wallet: share.wallet,
+                    weight_bps: share.weight_bps,
+                    owed: existing.map_or(0, |recipient| recipient.owed),
+                    total_claimed: existing.map_or(0, |recipient| recipient.total_claimed),
+                }
+            })
+            .collect();
+        for removed in self.recipients.iter().filter(|recipient| recipient.owed > 0) {
+            if !shares.iter().any(|share| share.wallet == removed.wallet) {
+                recipients.push(FeeSplitRecipient { weight_bps: 0, ..removed.clone() });
+            }
+        }
+        require!(recipients.len() <= MAX_FEE_SPLIT_RECIPIENTS, ErrorCode::FeeSplitUnsettled);
+        self.recipients = recipients;
+        Ok(())
+    }
+
+    // Pays out `wallet`'s owed fees, dropping it from the split if it no longer has a weight
+    pub fn claim(&mut self, wallet: Pubkey) -> Result<u64> {
+        let recipient = self.recipients
+            .iter_mut()
+            .find(|recipient| recipient.wallet == wallet)
+            .ok_or(ErrorCode::Unauthorized)?;
+        let amount = recipient.owed;
+        require!(amount > 0, ErrorCode::NoFeesToClaim);
+
+        recipient.owed = 0;
+        recipient.total_claimed = recipient.total_claimed.checked_add(amount)
+            .ok_or(ErrorCode::CalculationError)?;
+        self.recipients.retain(|recipient| recipient.weight_bps > 0 || recipient.owed > 0);
+        Ok(amount)
+    }
+}
+
+#[event]
+pub struct TokenInitialized {
+    pub token_account: Pubkey,
+    pub mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub creator_fee: u64,
+    pub platform_fee: u64,
+    pub authority: Pubkey,
+    pub treasury: Pubkey,
+    pub metadata_uri: String,
+    pub metadata_hash: [u8; 32],
+    pub decimals: u8,
+    pub max_supply: u64,
+}
+
+#[event]
+pub struct FeesUpdated {
+    pub token_account: Pubkey,
+    pub creator_fee: u64,
+    pub platform_fee: u64,
+    pub authority: Pubkey,
 }
 
-impl TokenHolder {
-    pub const LEN: usize = 32 + 8 + 8 + 8 + 1;
+#[event]
+pub struct AccountFrozen {
+    pub token_account: Pubkey,
+    pub authority: Pubkey,
 }
 
-#[account]
-pub struct LaunchRecord {
-    pub token_account: Pubkey,      // 32 bytes
-    pub launch_date: i64,           // 8 bytes
-    pub launch_data: String,        // Variable, up to 200 bytes
-    pub is_verified: bool,          // 1 byte
-    pub initial_market_cap: u64,    // 8 bytes
-    pub initial_holder_count: u16,  // 2 bytes
-    pub creator_wallet: Pubkey,     // 32 bytes
+#[event]
+pub struct AccountUnfrozen {
+    pub token_account: Pubkey,
+    pub authority: Pubkey,
 }
 
-impl LaunchRecord {
-    pub const LEN: usize = 32 + 8 + 200 + 1 + 8 + 2 + 32;
+#[event]
+pub struct TokensMinted {
+    pub token_account: Pubkey,
+    pub creator: Pubkey,
+    pub holder: Pubkey,
+    pub amount: u64,
+    pub treasury_amount: u64,
+    pub holder_amount: u64,
+    pub sniper_tax_amount: u64,
+    pub price_per_token: u64,
+    pub total_price: u64,
+    pub creator_fee: u64,
+    pub platform_fee: u64,
+    pub fee_tier: Option<u8>,
+    pub referrer: Option<Pubkey>,
+    pub referral_fee: u64,
+    pub is_bonding_curve_active: bool,
+    pub timestamp: i64,
 }
 
-// New struct for managing token trading parameters
-#[account]
-pub struct TradingConfig {
-    pub token_account: Pubkey,      // 32 bytes
-    pub min_trade_amount: u64,      // 8 bytes
-    pub max_slippage: u16,          // 2 bytes - in basis points (100 = 1%)
-    pub trading_enabled: bool,      // 1 byte
-    pub authority: Pubkey,          // 32 bytes
+#[event]
+pub struct TokensSold {
+    pub token_account: Pubkey,
+    pub seller: Pubkey,
+    pub holder: Pubkey,
+    pub amount: u64,
+    pub sol_out: u64,
+    pub creator_fee: u64,
+    pub platform_fee: u64,
+    pub fee_tier: Option<u8>,
+    pub referrer: Option<Pubkey>,
+    pub referral_fee: u64,
+    pub seller_receives: u64,
+    pub real_sol_reserve: u64,
+    pub market_cap: u64,
+    pub timestamp: i64,
 }
 
-impl TradingConfig {
-    pub const LEN: usize = 32 + 8 + 2 + 1 + 32;
+#[event]
+pub struct TokensBurned {
+    pub token_account: Pubkey,
+    pub owner: Pubkey,
+    pub holder: Pubkey,             // Default when burning the unsold allocation
+    pub amount: u64,
+    pub unsold_allocation: bool,
+    pub total_supply: u64,
+    pub max_supply: u64,
+    pub timestamp: i64,
 }
 
 #[event]
-pub struct TokenInitialized {
+pub struct TradeExecuted {
     pub token_account: Pubkey,
-    pub name: String,
-    pub symbol: String,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub price: u64,
+    pub trade_value: u64,
     pub creator_fee: u64,
     pub platform_fee: u64,
-    pub authority: Pubkey,
+    pub fee_tier: Option<u8>,
+    pub referrer: Option<Pubkey>,
+    pub referral_fee: u64,
+    pub seller_receives: u64,
+    pub transfer_fee: u64,
     pub treasury: Pubkey,
-    pub bonding_curve_cap: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TreasuryUpdated {
+    pub token_account: Pubkey,
+    pub old_treasury: Pubkey,
+    pub new_treasury: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CreatorFeesClaimed {
+    pub token_account: Pubkey,
+    pub creator: Pubkey,
+    pub milestone_reached: bool,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokenLaunchRecorded {
+    pub token_account: Pubkey,
+    pub launch_record: Pubkey,
+    pub launch_date: i64,
+    pub curve_type: CurveType,
+    pub metadata_hash: [u8; 32],
+    pub initial_market_cap: u64,
+    pub initial_holder_count: u16,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct TokenStatisticsVerified {
+    pub token_account: Pubkey,
+    pub oracle: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokenMetadataUpdated {
+    pub token_account: Pubkey,
+    pub new_uri: String,
+    pub content_hash: [u8; 32],
+    pub metadata_locked: bool,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MetadataLocked {
+    pub token_account: Pubkey,
+    pub metadata_uri: String,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OwnershipTransferred {
+    pub token_account: Pubkey,
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PlatformConfigInitialized {
+    pub platform_config: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AttesterRegistered {
+    pub attester: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AttesterRevoked {
+    pub attester: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct HolderVerified {
+    pub holder: Pubkey,
+    pub owner: Pubkey,
+    pub attester: Pubkey,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct HolderVerificationRevoked {
+    pub holder: Pubkey,
+    pub owner: Pubkey,
+    pub attester: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VerifiedHoldersOnlyUpdated {
+    pub token_account: Pubkey,
+    pub enabled: bool,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LaunchGuardConfigured {
+    pub token_account: Pubkey,
+    pub window_slots: u64,
+    pub max_wallet_buy: u64,
+    pub max_tx_buy: u64,
+    pub sniper_tax_bps: u64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TradingLimitsUpdated {
+    pub token_account: Pubkey,
+    pub max_wallet_bps: u64,
+    pub max_tx_bps: u64,
+    pub relax_after_graduation: bool,
+    pub reference_supply: u64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TradeCooldownUpdated {
+    pub token_account: Pubkey,
+    pub cooldown_seconds: i64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PresaleConfigured {
+    pub token_account: Pubkey,
+    pub presale: Pubkey,
+    pub merkle_root: [u8; 32],
+    pub max_per_wallet: u64,
+    pub price_per_token: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct PresalePurchased {
+    pub token_account: Pubkey,
+    pub presale: Pubkey,
+    pub buyer: Pubkey,
+    pub holder: Pubkey,
+    pub amount: u64,
+    pub price_per_token: u64,
+    pub total_price: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VestingCreated {
+    pub token_account: Pubkey,
+    pub vesting_schedule: Pubkey,
+    pub beneficiary: Pubkey,
+    pub total_amount: u64,
+    pub start_time: i64,
+    pub cliff_time: i64,
+    pub end_time: i64,
+}
+
+#[event]
+pub struct VestingReleased {
+    pub token_account: Pubkey,
+    pub vesting_schedule: Pubkey,
+    pub beneficiary: Pubkey,
+    pub holder: Pubkey,
+    pub amount: u64,
+    pub released_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LiquidityLocked {
+    pub token_account: Pubkey,
+    pub liquidity_lock: Pubkey,
+    pub lp_mint: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub unlock_time: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LiquidityLockExtended {
+    pub token_account: Pubkey,
+    pub liquidity_lock: Pubkey,
+    pub old_unlock_time: i64,
+    pub new_unlock_time: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LiquidityUnlocked {
+    pub token_account: Pubkey,
+    pub liquidity_lock: Pubkey,
+    pub lp_mint: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VerifierRegistered {
+    pub verifier: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VerifierRevoked {
+    pub verifier: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LaunchRecordVerified {
+    pub token_account: Pubkey,
+    pub launch_record: Pubkey,
+    pub verifier: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub version: u8,
+    pub payer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CurveConfigured {
+    pub token_account: Pubkey,
+    pub virtual_sol_reserve: u64,
+    pub virtual_token_reserve: u64,
+    pub graduation_reserve_target: u64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CurveGraduated {
+    pub token_account: Pubkey,
+    pub real_sol_reserve: u64,
+    pub tokens_sold: u64,
+    pub market_cap: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BuybackConfigured {
+    pub token_account: Pubkey,
+    pub buyback_bps: u64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
 }
 
 #[event]
-pub struct FeesUpdated {
+pub struct BuybackExecuted {
     pub token_account: Pubkey,
-    pub creator_fee: u64,
-    pub platform_fee: u64,
-    pub authority: Pubkey,
+    pub cranker: Pubkey,
+    pub sol_spent: u64,
+    pub tokens_burned: u64,
+    pub buyback_remaining: u64,
+    pub timestamp: i64,
 }
 
 #[event]
-pub struct AccountFrozen {
-    pub token_account: Pubkey,
+pub struct ReferrerUpdated {
+    pub wallet: Pubkey,
+    pub share_bps: u64,
+    pub is_active: bool,
     pub authority: Pubkey,
+    pub timestamp: i64,
 }
 
 #[event]
-pub struct AccountUnfrozen {
-    pub token_account: Pubkey,
+pub struct ReferralFeesClaimed {
+    pub wallet: Pubkey,
+    pub amount: u64,
+    pub total_earned: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeeTiersUpdated {
+    pub fee_tiers: Vec<FeeTier>,
     pub authority: Pubkey,
+    pub timestamp: i64,
 }
 
 #[event]
-pub struct TokensMinted {
+pub struct FeeSplitProposed {
     pub token_account: Pubkey,
-    pub creator: Pubkey,
-    pub holder: Pubkey,
-    pub amount: u64,
-    pub treasury_amount: u64,
-    pub holder_amount: u64,
-    pub price_per_token: u64,
-    pub total_price: u64,
-    pub is_bonding_curve_active: bool,
+    pub shares: Vec<FeeShare>,
+    pub effective_at: i64,
+    pub authority: Pubkey,
     pub timestamp: i64,
 }
 
 #[event]
-pub struct TradeExecuted {
+pub struct FeeSplitApplied {
     pub token_account: Pubkey,
-    pub seller: Pubkey,
-    pub buyer: Pubkey,
-    pub creator: Pubkey,
-    pub amount: u64,
-    pub price: u64,
-    pub trade_value: u64,
-    pub creator_fee: u64,
-    pub platform_fee: u64,
-    pub seller_receives: u64,
-    pub treasury: Pubkey,
+    pub shares: Vec<FeeShare>,
+    pub creator_payout: u64,        // Fees collected before the first split, paid to the creator
     pub timestamp: i64,
 }
 
 #[event]
-pub struct TreasuryUpdated {
+pub struct FeeSplitClaimed {
     pub token_account: Pubkey,
-    pub old_treasury: Pubkey,
-    pub new_treasury: Pubkey,
-    pub authority: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
     pub timestamp: i64,
 }
 
 #[event]
-pub struct CreatorFeesClaimed {
+pub struct TransferFeesWithdrawn {
     pub token_account: Pubkey,
-    pub creator: Pubkey,
-    pub milestone_reached: bool,
+    pub mint: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
     pub timestamp: i64,
 }
 
 #[event]
-pub struct TokenLaunchRecorded {
+pub struct PoolLpMintRecorded {
     pub token_account: Pubkey,
-    pub launch_record: Pubkey,
-    pub launch_date: i64,
+    pub lp_mint: Pubkey,
     pub authority: Pubkey,
+    pub timestamp: i64,
 }
 
 #[event]
-pub struct TokenStatisticsVerified {
+pub struct HolderMigrated {
     pub token_account: Pubkey,
-    pub oracle: Pubkey,
+    pub legacy_holder: Pubkey,
+    pub holder: Pubkey,
+    pub owner: Pubkey,
+    pub balance: u64,
     pub timestamp: i64,
 }
 
 #[event]
-pub struct TokenMetadataUpdated {
+pub struct MintAttached {
     pub token_account: Pubkey,
-    pub new_uri: String,
+    pub mint: Pubkey,
     pub authority: Pubkey,
     pub timestamp: i64,
 }
 
 #[event]
-pub struct OwnershipTransferred {
-    pub token_account: Pubkey,
-    pub old_authority: Pubkey,
-    pub new_authority: Pubkey,
+pub struct PlatformFeesClaimed {
+    pub platform_fee_vault: Pubkey,
+    pub authority: Pubkey,
+    pub amount: u64,
     pub timestamp: i64,
 }
 
@@ -805,4 +5874,1078 @@ pub enum ErrorCode {
     SlippageExceeded,
     #[msg("Trade amount below minimum threshold")]
     TradeBelowMinimum,
+    #[msg("Holder is not KYC verified or verification has expired")]
+    HolderNotVerified,
+    #[msg("Attester is not registered or has been revoked")]
+    AttesterNotActive,
+    #[msg("Verification expiry must be in the future")]
+    InvalidVerificationExpiry,
+    #[msg("Buy exceeds the per-transaction limit during the launch window")]
+    SniperTxLimitExceeded,
+    #[msg("Buy exceeds the per-wallet limit during the launch window")]
+    SniperWalletLimitExceeded,
+    #[msg("Sniper tax cannot exceed 50%")]
+    InvalidSniperTax,
+    #[msg("Launch guard cannot be changed after tokens have been minted")]
+    LaunchGuardLocked,
+    #[msg("Trading limits must be between 0 and 10000 basis points of a non-zero reference supply")]
+    InvalidTradingLimits,
+    #[msg("Holder balance would exceed the max wallet limit")]
+    MaxWalletExceeded,
+    #[msg("Amount exceeds the max transaction limit")]
+    MaxTransactionExceeded,
+    #[msg("Trade cooldown must be between 0 seconds and 1 day")]
+    InvalidTradeCooldown,
+    #[msg("Holder must wait for the trade cooldown to pass")]
+    TradeCooldownActive,
+    #[msg("Public trading is closed until the presale ends")]
+    PresaleActive,
+    #[msg("Presale is not currently active")]
+    PresaleNotActive,
+    #[msg("Presale must be configured before any tokens are minted")]
+    PresaleAfterLaunch,
+    #[msg("Presale start time must be before a future end time")]
+    InvalidPresaleWindow,
+    #[msg("Presale price and wallet cap must be greater than zero")]
+    InvalidPresaleConfig,
+    #[msg("Wallet is not on the presale allowlist")]
+    InvalidMerkleProof,
+    #[msg("Purchase exceeds the presale per-wallet cap")]
+    PresaleWalletCapExceeded,
+    #[msg("Launch record does not belong to this token")]
+    InvalidLaunchRecord,
+    #[msg("Vesting amount must be positive and the cliff cannot exceed the duration")]
+    InvalidVestingSchedule,
+    #[msg("No vested tokens are available to release")]
+    NothingToRelease,
+    #[msg("Token has not graduated from the bonding curve")]
+    NotGraduated,
+    #[msg("Liquidity lock amount must be positive and the unlock time must move forward")]
+    InvalidLiquidityLock,
+    #[msg("Verifier is not registered or has been revoked")]
+    VerifierNotActive,
+    #[msg("Launch record is already verified")]
+    LaunchAlreadyVerified,
+    #[msg("Launch parameters are invalid")]
+    InvalidLaunchParams,
+    #[msg("Metaplex metadata account is required for this mint")]
+    MissingMetadataAccount,
+    #[msg("Token metadata is locked and can no longer be updated")]
+    MetadataLocked,
+    #[msg("Metadata URI must use https://, ipfs:// or ar://")]
+    UnsupportedMetadataUriScheme,
+    #[msg("Metadata URI contains control or whitespace characters")]
+    InvalidMetadataUriCharacters,
+    #[msg("Metadata content hash cannot be empty")]
+    InvalidMetadataHash,
+    #[msg("Account is not a v1 account of the expected type")]
+    InvalidAccountVersion,
+    #[msg("Account has already been migrated to the current layout")]
+    AccountAlreadyMigrated,
+    #[msg("Treasury account does not match the token's treasury")]
+    InvalidTreasury,
+    #[msg("Sum of balances does not match total supply")]
+    SupplyInvariantViolated,
+    #[msg("No fees are available to claim")]
+    NoFeesToClaim,
+    #[msg("Bonding curve has graduated and no longer fills trades")]
+    CurveNotActive,
+    #[msg("Bonding curve does not hold enough liquidity for this trade")]
+    InsufficientCurveLiquidity,
+    #[msg("Bonding curve cannot be changed after tokens have been minted")]
+    CurveLocked,
+    #[msg("Curve reserves and graduation target must be greater than zero")]
+    InvalidCurveConfig,
+    #[msg("Decimals must be at most 9 and max supply greater than zero")]
+    InvalidSupplyConfig,
+    #[msg("Amount exceeds the remaining allocation under the max supply")]
+    MaxSupplyExceeded,
+    #[msg("Burn amount must be greater than zero")]
+    InvalidBurnAmount,
+    #[msg("The token's mint and token program accounts are required")]
+    MintAccountsRequired,
+    #[msg("No unsold allocation is left to burn")]
+    NoUnsoldAllocation,
+    #[msg("Buyback share must be between 0 and 10000 basis points")]
+    InvalidBuybackShare,
+    #[msg("Buyback interval has not passed since the last buyback")]
+    BuybackTooSoon,
+    #[msg("Not enough committed fees to buy back any tokens")]
+    NothingToBuyBack,
+    #[msg("Referral share must be between 0 and 10000 basis points")]
+    InvalidReferralShare,
+    #[msg("Referrer is not registered or has been deactivated")]
+    ReferrerNotActive,
+    #[msg("Traders cannot refer themselves")]
+    SelfReferral,
+    #[msg("Fee tiers must have rising volume thresholds, non-decreasing discounts of at most 10000 basis points and fit the tier limit")]
+    InvalidFeeTiers,
+    #[msg("Fee split needs 1 to 10 distinct recipients with non-zero weights summing to 10000 basis points")]
+    InvalidFeeSplit,
+    #[msg("Creator fees are split; recipients claim them through the fee split")]
+    FeeSplitActive,
+    #[msg("No fee split change is pending")]
+    NoPendingFeeSplit,
+    #[msg("Too many removed recipients still have fees to claim to apply this split")]
+    FeeSplitUnsettled,
+    #[msg("Vesting schedules would escrow more than the allowed share of the supply")]
+    VestingCapExceeded,
+    #[msg("The token's pool LP mint has already been recorded")]
+    PoolLpMintAlreadySet,
+    #[msg("LP mint is not the recorded pool LP mint of this token")]
+    LpMintMismatch,
+    #[msg("Token is not backed by a Token-2022 mint")]
+    NotToken2022,
+    #[msg("Seller and buyer must be different wallets")]
+    SelfTrade,
+    #[msg("Only tokens bought from the curve can be sold back into it")]
+    ExceedsCurveBalance,
+    #[msg("The curve's virtual token reserve must exceed the max supply")]
+    CurveReserveBelowMaxSupply,
+    #[msg("The token is already backed by a mint")]
+    MintAlreadyAttached,
+    #[msg("The token has no mint yet; attach one first")]
+    MintNotAttached,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A token as initialize leaves it, before anything is minted
+    fn token() -> TokenAccount {
+        TokenAccount {
+            version: CURRENT_ACCOUNT_VERSION,
+            name: "Token".to_string(),
+            symbol: "TKN".to_string(),
+            creator_fee: 250,
+            platform_fee: 250,
+            authority: Pubkey::new_unique(),
+            treasury: Pubkey::new_unique(),
+            total_supply: 0,
+            is_frozen: false,
+            is_bonding_curve_active: true,
+            market_cap: 0,
+            metadata_uri: "https://example.com/token.json".to_string(),
+            metadata_hash: [0u8; 32],
+            last_updated_at: 0,
+            creation_time: 1_700_000_000,
+            verified: false,
+            verified_holders_only: false,
+            creation_slot: 0,
+            sniper_window_slots: 0,
+            sniper_max_wallet_buy: 0,
+            sniper_max_tx_buy: 0,
+            sniper_tax_bps: 0,
+            max_wallet_bps: 0,
+            max_tx_bps: 0,
+            relax_limits_after_graduation: false,
+            limit_reference_supply: 0,
+            trade_cooldown_seconds: 0,
+            presale_end_time: 0,
+            holder_count: 0,
+            mint: Pubkey::new_unique(),
+            mint_authority_bump: 255,
+            token_program: anchor_spl::token::ID,
+            metadata_locked: false,
+            virtual_sol_reserve: DEFAULT_VIRTUAL_SOL_RESERVE,
+            virtual_token_reserve: default_virtual_token_reserve(TOKEN_DECIMALS),
+            real_sol_reserve: 0,
+            tokens_sold: 0,
+            graduation_reserve_target: DEFAULT_GRADUATION_RESERVE_TARGET,
+            decimals: TOKEN_DECIMALS,
+            max_supply: 0,
+            buyback_bps: 0,
+            fee_split_enabled: false,
+            total_held: 0,
+            total_escrowed: 0,
+            pool_lp_mint: Pubkey::default(),
+            legacy_supply: 0,
+            reserved: [0u8; 32],
+        }
+    }
+
+    // A holder account created on its owner's first buy
+    fn holder() -> TokenHolder {
+        TokenHolder {
+            version: CURRENT_ACCOUNT_VERSION,
+            owner: Pubkey::new_unique(),
+            token_account: Pubkey::new_unique(),
+            balance: 0,
+            last_trade: 0,
+            initial_entry: 0,
+            is_verified: false,
+            verification_expiry: 0,
+            launch_window_bought: 0,
+            curve_balance: 0,
+            bump: 255,
+            reserved: [0u8; 32],
+        }
+    }
+
+    // Mints `amount` into `holder`'s balance the way the handlers do
+    fn mint_to(account: &mut TokenAccount, holder: &mut TokenHolder, amount: u64) {
+        account.add_supply(amount).unwrap();
+        account.track_holder_balance(holder.balance, holder.balance + amount).unwrap();
+        holder.balance += amount;
+    }
+
+    fn v1_token_account_fixture() -> TokenAccountV1 {
+        TokenAccountV1 {
+            name: "N".repeat(32),
+            symbol: "S".repeat(8),
+            creator_fee: 250,
+            platform_fee: 250,
+            authority: Pubkey::new_unique(),
+            treasury: Pubkey::new_unique(),
+            total_supply: 1_000_000,
+            is_frozen: false,
+            is_bonding_curve_active: true,
+            bonding_curve_cap: 50000,
+            market_cap: 42_000,
+            metadata_uri: "https://example.com/token.json".to_string(),
+            last_updated_at: 1_700_000_000,
+            creation_time: 1_690_000_000,
+            verified: true,
+        }
+    }
+
+    // Lays out a v1 account exactly as it was allocated on chain
+    fn v1_account_data<T: AnchorSerialize>(discriminator: [u8; 8], v1_len: usize, account: &T) -> Vec<u8> {
+        let mut data = discriminator.to_vec();
+        account.serialize(&mut data).unwrap();
+        data.resize(8 + v1_len, 0);
+        data
+    }
+
+    #[test]
+    fn migrates_v1_token_account_fixture() {
+        let fixture = v1_token_account_fixture();
+        let data = v1_account_data(TokenAccount::DISCRIMINATOR, TokenAccountV1::LEN, &fixture);
+
+        let v1 = deserialize_v1::<TokenAccountV1>(&data, &TokenAccount::DISCRIMINATOR, TokenAccountV1::LEN)
+            .unwrap();
+        let migrated = TokenAccount::from_v1(v1);
+
+        let mut new_data = vec![0u8; 8 + TokenAccount::INIT_SPACE];
+        migrated.try_serialize(&mut &mut new_data[..]).unwrap();
+        let mut upgraded = TokenAccount::try_deserialize(&mut &new_data[..]).unwrap();
+
+        assert_eq!(upgraded.version, CURRENT_ACCOUNT_VERSION);
+        assert_eq!(upgraded.name, fixture.name);
+        assert_eq!(upgraded.symbol, fixture.symbol);
+        assert_eq!(upgraded.creator_fee, fixture.creator_fee);
+        assert_eq!(upgraded.platform_fee, fixture.platform_fee);
+        assert_eq!(upgraded.authority, fixture.authority);
+        assert_eq!(upgraded.treasury, fixture.treasury);
+        assert_eq!(upgraded.total_supply, fixture.total_supply);
+        assert_eq!((upgraded.total_held, upgraded.legacy_supply), (0, fixture.total_supply));
+        assert_eq!(upgraded.mint, Pubkey::default());
+        upgraded.check_supply_invariant().unwrap();
+        assert_eq!(upgraded.market_cap, fixture.market_cap);
+        assert_eq!(upgraded.metadata_uri, fixture.metadata_uri);
+        assert_eq!(upgraded.creation_time, fixture.creation_time);
+        assert!(upgraded.verified);
+        assert!(!upgraded.metadata_locked);
+
+        // Migrated holders are credited out of the legacy supply, never beyond it
+        let mut owner = holder();
+        upgraded.credit_legacy_balance(&mut owner, fixture.total_supply).unwrap();
+        assert_eq!((upgraded.total_held, upgraded.legacy_supply), (fixture.total_supply, 0));
+        upgraded.check_supply_invariant().unwrap();
+        assert_eq!(
+            upgraded.credit_legacy_balance(&mut owner, 1).unwrap_err(),
+            error!(ErrorCode::SupplyInvariantViolated)
+        );
+    }
+
+    #[test]
+    fn migrates_v1_token_holder_fixture() {
+        let fixture = TokenHolderV1 {
+            owner: Pubkey::new_unique(),
+            balance: 12_345,
+            last_trade: 1_700_000_100,
+            initial_entry: 1_700_000_000,
+            is_verified: true,
+        };
+        let data = v1_account_data(TokenHolder::DISCRIMINATOR, TokenHolderV1::LEN, &fixture);
+
+        let v1 = deserialize_v1::<TokenHolderV1>(&data, &TokenHolder::DISCRIMINATOR, TokenHolderV1::LEN)
+            .unwrap();
+        let token_account = Pubkey::new_unique();
+        let migrated = TokenHolder::from_v1(v1, token_account, 254);
+
+        let mut new_data = [0u8; 8 + TokenHolder::INIT_SPACE];
+        migrated.try_serialize(&mut &mut new_data[..]).unwrap();
+        let upgraded = TokenHolder::try_deserialize(&mut &new_data[..]).unwrap();
+
+        assert_eq!(upgraded.version, CURRENT_ACCOUNT_VERSION);
+        assert_eq!(upgraded.owner, fixture.owner);
+        assert_eq!(upgraded.balance, fixture.balance);
+        assert_eq!(upgraded.last_trade, fixture.last_trade);
+        assert_eq!(upgraded.initial_entry, fixture.initial_entry);
+        assert!(upgraded.is_verified);
+        assert_eq!(upgraded.verification_expiry, 0);
+        assert_eq!((upgraded.token_account, upgraded.bump), (token_account, 254));
+        assert_eq!(upgraded.curve_balance, 0);
+    }
+
+    #[test]
+    fn rejects_already_migrated_account() {
+        let mut data = vec![0u8; 8 + TokenAccount::INIT_SPACE];
+        token().try_serialize(&mut &mut data[..]).unwrap();
+
+        let result = deserialize_v1::<TokenAccountV1>(&data, &TokenAccount::DISCRIMINATOR, TokenAccountV1::LEN);
+        assert_eq!(result.unwrap_err(), error!(ErrorCode::AccountAlreadyMigrated));
+    }
+
+    // Serializes `account` and checks it fits the space it is allocated with
+    fn assert_fits<T: AccountSerialize + Space>(account: &T) {
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        assert!(
+            data.len() <= 8 + T::INIT_SPACE,
+            "serialized {} bytes into {} bytes of space",
+            data.len(),
+            8 + T::INIT_SPACE
+        );
+    }
+
+    #[test]
+    fn worst_case_token_account_fits() {
+        let metadata_uri = format!("https://{}", "u".repeat(192));
+        assert_eq!(metadata_uri.len(), 200);
+
+        assert_fits(&TokenAccount {
+            name: "N".repeat(32),
+            symbol: "S".repeat(8),
+            metadata_uri,
+            metadata_hash: [u8::MAX; 32],
+            reserved: [u8::MAX; 32],
+            ..token()
+        });
+    }
+
+    #[test]
+    fn worst_case_token_holder_fits() {
+        assert_fits(&TokenHolder {
+            balance: u64::MAX,
+            last_trade: i64::MAX,
+            initial_entry: i64::MAX,
+            is_verified: true,
+            verification_expiry: i64::MAX,
+            launch_window_bought: u64::MAX,
+            curve_balance: u64::MAX,
+            bump: u8::MAX,
+            reserved: [u8::MAX; 32],
+            ..holder()
+        });
+    }
+
+    #[test]
+    fn worst_case_launch_record_fits() {
+        assert_fits(&LaunchRecord {
+            token_account: Pubkey::new_unique(),
+            launch_date: i64::MAX,
+            launch_params: LaunchParams {
+                initial_price: u64::MAX,
+                curve_type: CurveType::Quadratic,
+                dev_buy_amount: u64::MAX,
+                socials_hash: [u8::MAX; 32],
+                metadata_hash: [u8::MAX; 32],
+            },
+            is_verified: true,
+            initial_market_cap: u64::MAX,
+            initial_holder_count: u16::MAX,
+            creator_wallet: Pubkey::new_unique(),
+            presale_tokens_sold: u64::MAX,
+            presale_proceeds: u64::MAX,
+            verified_by: Pubkey::new_unique(),
+            bump: u8::MAX,
+        });
+    }
+
+    #[test]
+    fn worst_case_platform_config_fits() {
+        assert_fits(&PlatformConfig {
+            authority: Pubkey::new_unique(),
+            fee_tiers: vec![FeeTier { min_volume: u64::MAX, discount_bps: u64::MAX }; MAX_FEE_TIERS],
+            bump: u8::MAX,
+        });
+    }
+
+    #[test]
+    fn fixed_size_accounts_fit() {
+        assert_fits(&Attester {
+            attester: Pubkey::new_unique(),
+            is_active: true,
+            registered_at: i64::MAX,
+            bump: u8::MAX,
+        });
+        assert_fits(&PresaleConfig {
+            token_account: Pubkey::new_unique(),
+            merkle_root: [u8::MAX; 32],
+            max_per_wallet: u64::MAX,
+            price_per_token: u64::MAX,
+            start_time: i64::MAX,
+            end_time: i64::MAX,
+            tokens_sold: u64::MAX,
+            proceeds: u64::MAX,
+            bump: u8::MAX,
+        });
+        assert_fits(&VestingSchedule {
+            token_account: Pubkey::new_unique(),
+            beneficiary: Pubkey::new_unique(),
+            total_amount: u64::MAX,
+            released_amount: u64::MAX,
+            start_time: i64::MAX,
+            cliff_time: i64::MAX,
+            end_time: i64::MAX,
+            bump: u8::MAX,
+        });
+        assert_fits(&LiquidityLock {
+            token_account: Pubkey::new_unique(),
+            lp_mint: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            lp_vault: Pubkey::new_unique(),
+            amount: u64::MAX,
+            locked_at: i64::MAX,
+            unlock_time: i64::MAX,
+            bump: u8::MAX,
+        });
+    }
+
+    #[test]
+    fn rejects_v1_account_of_another_type() {
+        let data = v1_account_data(TokenHolder::DISCRIMINATOR, TokenAccountV1::LEN, &v1_token_account_fixture());
+
+        let result = deserialize_v1::<TokenAccountV1>(&data, &TokenAccount::DISCRIMINATOR, TokenAccountV1::LEN);
+        assert_eq!(result.unwrap_err(), error!(ErrorCode::InvalidAccountVersion));
+    }
+
+    #[test]
+    fn verification_lapses_at_expiry() {
+        let mut holder = holder();
+        assert!(!holder.is_verification_active(1_000));
+
+        holder.is_verified = true;
+        holder.verification_expiry = 2_000;
+        assert!(holder.is_verification_active(1_999));
+        assert!(!holder.is_verification_active(2_000));
+
+        holder.is_verified = false;
+        assert!(!holder.is_verification_active(1_000));
+    }
+
+    #[test]
+    fn trade_cooldown_blocks_until_it_elapses() {
+        let mut holder = holder();
+        // A holder that never traded is not cooling down
+        holder.check_trade_cooldown(1_000, 60).unwrap();
+
+        holder.record_trade(1_000);
+        assert_eq!(holder.initial_entry, 1_000);
+        assert_eq!(
+            holder.check_trade_cooldown(1_059, 60).unwrap_err(),
+            error!(ErrorCode::TradeCooldownActive)
+        );
+        holder.check_trade_cooldown(1_060, 60).unwrap();
+        holder.check_trade_cooldown(1_001, 0).unwrap();
+
+        holder.record_trade(1_060);
+        assert_eq!(holder.initial_entry, 1_000);
+        assert_eq!(holder.last_trade, 1_060);
+    }
+
+    #[test]
+    fn launch_window_covers_the_configured_slots() {
+        let mut account = TokenAccount { creation_slot: 100, ..token() };
+        assert!(!account.is_in_launch_window(100));
+
+        account.sniper_window_slots = 10;
+        assert!(account.is_in_launch_window(100));
+        assert!(account.is_in_launch_window(109));
+        assert!(!account.is_in_launch_window(110));
+    }
+
+    #[test]
+    fn merkle_proof_accepts_members_only() {
+        let leaves: Vec<[u8; 32]> = (0..4u8)
+            .map(|i| keccak::hashv(&[Pubkey::new_from_array([i; 32]).as_ref()]).0)
+            .collect();
+        let pair = |a: [u8; 32], b: [u8; 32]| {
+            if a <= b { keccak::hashv(&[&a, &b]).0 } else { keccak::hashv(&[&b, &a]).0 }
+        };
+        let left = pair(leaves[0], leaves[1]);
+        let right = pair(leaves[2], leaves[3]);
+        let root = pair(left, right);
+
+        assert!(verify_merkle_proof(&[leaves[1], right], root, leaves[0]));
+        assert!(verify_merkle_proof(&[leaves[2], left], root, leaves[3]));
+        assert!(!verify_merkle_proof(&[leaves[1], right], root, leaves[2]));
+        assert!(!verify_merkle_proof(&[leaves[1]], root, leaves[0]));
+
+        let outsider = keccak::hashv(&[Pubkey::new_unique().as_ref()]).0;
+        assert!(!verify_merkle_proof(&[leaves[1], right], root, outsider));
+    }
+
+    #[test]
+    fn metadata_uri_validation() {
+        validate_metadata_uri("https://example.com/token.json").unwrap();
+        validate_metadata_uri("ipfs://bafy").unwrap();
+        validate_metadata_uri("ar://tx").unwrap();
+
+        assert_eq!(validate_metadata_uri("").unwrap_err(), error!(ErrorCode::InvalidMetadataUri));
+        assert_eq!(validate_metadata_uri("https://").unwrap_err(), error!(ErrorCode::InvalidMetadataUri));
+        assert_eq!(
+            validate_metadata_uri("http://example.com").unwrap_err(),
+            error!(ErrorCode::UnsupportedMetadataUriScheme)
+        );
+        assert_eq!(
+            validate_metadata_uri("https://example.com/a b").unwrap_err(),
+            error!(ErrorCode::InvalidMetadataUriCharacters)
+        );
+        let long_uri = format!("https://{}", "a".repeat(193));
+        assert_eq!(validate_metadata_uri(&long_uri).unwrap_err(), error!(ErrorCode::MetadataUriTooLong));
+    }
+
+    #[test]
+    fn fee_tiers_must_rise_in_volume_and_discount() {
+        validate_fee_tiers(&[]).unwrap();
+        let tier = |min_volume, discount_bps| FeeTier { min_volume, discount_bps };
+
+        validate_fee_tiers(&[tier(100, 1000), tier(200, 1000)]).unwrap();
+        assert!(validate_fee_tiers(&[tier(200, 1000), tier(100, 2000)]).is_err());
+        assert!(validate_fee_tiers(&[tier(100, 1000), tier(100, 2000)]).is_err());
+        assert!(validate_fee_tiers(&[tier(100, 2000), tier(200, 1000)]).is_err());
+        assert!(validate_fee_tiers(&[tier(100, 10001)]).is_err());
+        assert!(validate_fee_tiers(&vec![tier(1, 0); MAX_FEE_TIERS + 1]).is_err());
+
+        let config = PlatformConfig {
+            authority: Pubkey::new_unique(),
+            fee_tiers: vec![tier(100, 1000), tier(200, 2000)],
+            bump: 255,
+        };
+        assert_eq!(config.fee_tier_for(99), None);
+        assert_eq!(config.fee_tier_for(100).map(|(index, _)| index), Some(0));
+        assert_eq!(config.fee_tier_for(u64::MAX).map(|(index, _)| index), Some(1));
+    }
+
+    #[test]
+    fn mint_split_rounds_treasury_share_down_without_losing_tokens() {
+        for amount in 0..100 {
+            assert_eq!(split_mint_amount(amount, 0).unwrap(), (0, amount));
+        }
+        assert_eq!(split_mint_amount(100, 0).unwrap(), (1, 99));
+        assert_eq!(split_mint_amount(199, 0).unwrap(), (1, 198));
+        assert_eq!(split_mint_amount(99, 10).unwrap(), (10, 89));
+
+        for amount in (0..1_000).chain([u64::MAX / 2, u64::MAX]) {
+            let sniper_tax_amount = bps_of(amount, MAX_SNIPER_TAX_BPS).unwrap();
+            let (treasury_amount, holder_amount) =
+                split_mint_amount(amount, sniper_tax_amount).unwrap();
+            assert_eq!(treasury_amount as u128 + holder_amount as u128, amount as u128);
+        }
+    }
+
+    #[test]
+    fn balances_track_supply_across_small_mints() {
+        let mut account = token();
+        let mut treasury_balance = 0u64;
+        let mut holder_balances = [0u64; 3];
+
+        for (i, amount) in [1u64, 50, 99, 100, 101, 250, 7].iter().enumerate() {
+            let (treasury_amount, holder_amount) = split_mint_amount(*amount, 0).unwrap();
+            account.add_supply(*amount).unwrap();
+            account.track_holder_balance(treasury_balance, treasury_balance + treasury_amount).unwrap();
+            treasury_balance += treasury_amount;
+            let holder_balance = &mut holder_balances[i % 3];
+            account.track_holder_balance(*holder_balance, *holder_balance + holder_amount).unwrap();
+            *holder_balance += holder_amount;
+
+            account.check_supply_invariant().unwrap();
+        }
+        assert_eq!(treasury_balance, 4);
+        assert_eq!(account.holder_count, 4);
+    }
+
+    #[test]
+    fn supply_invariant_counts_escrow_and_detects_drift() {
+        let mut account = token();
+        account.check_supply_invariant().unwrap();
+        let mut creator = holder();
+        mint_to(&mut account, &mut creator, 1_000_000);
+        account.check_supply_invariant().unwrap();
+
+        // Escrowed tokens stay in the supply
+        account.escrow_vesting(&mut creator, 100_000).unwrap();
+        account.check_supply_invariant().unwrap();
+
+        // Supply must be matched by a balance or escrow: minting into escrow keeps the invariant,
+        // minting into neither breaks it
+        account.add_supply(100_000).unwrap();
+        account.total_escrowed += 100_000;
+        account.check_supply_invariant().unwrap();
+        let mut free_mint = account.clone();
+        free_mint.add_supply(100_000).unwrap();
+        assert_eq!(
+            free_mint.check_supply_invariant().unwrap_err(),
+            error!(ErrorCode::SupplyInvariantViolated)
+        );
+
+        // So does crediting a balance without minting, or burning without debiting one
+        let mut free_credit = account.clone();
+        free_credit.track_holder_balance(0, 1).unwrap();
+        assert_eq!(
+            free_credit.check_supply_invariant().unwrap_err(),
+            error!(ErrorCode::SupplyInvariantViolated)
+        );
+        account.burn_supply(1).unwrap();
+        assert_eq!(
+            account.check_supply_invariant().unwrap_err(),
+            error!(ErrorCode::SupplyInvariantViolated)
+        );
+    }
+
+    #[test]
+    fn vesting_escrows_creator_tokens_without_raising_supply() {
+        let mut account = token();
+        let mut creator = holder();
+        mint_to(&mut account, &mut creator, 300_000);
+        mint_to(&mut account, &mut holder(), 700_000);
+        let mut beneficiary = holder();
+
+        // Up to 20% of the 1_000_000 supply can sit in escrow, and only out of the creator's balance
+        account.escrow_vesting(&mut creator, 150_000).unwrap();
+        assert_eq!(account.total_supply, 1_000_000);
+        assert_eq!(creator.balance, 150_000);
+        assert_eq!(account.total_escrowed, 150_000);
+        assert_eq!(
+            account.escrow_vesting(&mut creator, 50_001).unwrap_err(),
+            error!(ErrorCode::VestingCapExceeded)
+        );
+        assert_eq!(
+            account.escrow_vesting(&mut beneficiary, 1).unwrap_err(),
+            error!(ErrorCode::InsufficientFunds)
+        );
+
+        account.release_escrow(&mut beneficiary, 150_000).unwrap();
+        assert_eq!(account.total_escrowed, 0);
+        assert_eq!(account.total_supply, 1_000_000);
+        assert_eq!(creator.balance + beneficiary.balance, 300_000);
+        assert!(account.release_escrow(&mut beneficiary, 1).is_err());
+    }
+
+    #[test]
+    fn only_curve_bought_tokens_can_be_sold_back() {
+        // 900 bought from the curve, 500 from the presale and 100 released from vesting
+        let mut buyer = TokenHolder { balance: 1_500, curve_balance: 900, ..holder() };
+        let mut other = holder();
+
+        assert_eq!(
+            buyer.clone().debit_curve_balance(901).unwrap_err(),
+            error!(ErrorCode::ExceedsCurveBalance)
+        );
+
+        // Trades move curve-bought tokens first
+        buyer.balance -= 1_000;
+        other.balance += 1_000;
+        buyer.transfer_curve_balance(&mut other, 1_000).unwrap();
+        assert_eq!((buyer.curve_balance, other.curve_balance), (0, 900));
+        assert!(buyer.debit_curve_balance(1).is_err());
+
+        // Burns and vesting take from the rest of the balance first
+        other.balance -= 100;
+        other.clamp_curve_balance();
+        assert_eq!(other.curve_balance, 900);
+        other.balance -= 100;
+        other.clamp_curve_balance();
+        assert_eq!(other.curve_balance, 800);
+        other.debit_curve_balance(800).unwrap();
+    }
+
+    #[test]
+    fn curve_buy_then_sell_never_drains_reserve() {
+        let mut account = token();
+
+        let unit = account.token_unit();
+        for amount in [1u64, 999, 1_000_000 * unit, 250_000_000 * unit] {
+            let cost = curve_buy_cost(account.virtual_sol_reserve, account.virtual_token_reserve, amount).unwrap();
+            account.apply_curve_buy(amount, cost).unwrap();
+            let proceeds = curve_sell_proceeds(account.virtual_sol_reserve, account.virtual_token_reserve, amount).unwrap();
+            assert!(proceeds <= cost);
+            account.apply_curve_sell(amount, proceeds).unwrap();
+        }
+        assert_eq!(account.tokens_sold, 0);
+        assert!(account.virtual_sol_reserve >= DEFAULT_VIRTUAL_SOL_RESERVE);
+        assert_eq!(account.virtual_token_reserve, default_virtual_token_reserve(TOKEN_DECIMALS));
+    }
+
+    #[test]
+    fn curve_price_rises_with_buys_and_values_supply() {
+        let mut account = token();
+        let start_price = account.curve_price().unwrap();
+
+        let amount = 500_000_000 * account.token_unit();
+        let cost = curve_buy_cost(account.virtual_sol_reserve, account.virtual_token_reserve, amount).unwrap();
+        account.apply_curve_buy(amount, cost).unwrap();
+        account.total_supply = amount;
+        account.refresh_market_cap().unwrap();
+
+        assert!(account.curve_price().unwrap() > start_price);
+        assert_eq!(account.real_sol_reserve, cost);
+        assert_eq!(
+            account.market_cap as u128,
+            account.virtual_sol_reserve as u128 * amount as u128 / account.virtual_token_reserve as u128
+        );
+    }
+
+    #[test]
+    fn curve_rejects_buys_past_the_token_reserve_and_oversized_sells() {
+        assert_eq!(
+            curve_buy_cost(DEFAULT_VIRTUAL_SOL_RESERVE, DEFAULT_VIRTUAL_TOKEN_RESERVE, DEFAULT_VIRTUAL_TOKEN_RESERVE)
+                .unwrap_err(),
+            error!(ErrorCode::InsufficientCurveLiquidity)
+        );
+
+        let mut account = token();
+        assert_eq!(
+            account.apply_curve_sell(1, 0).unwrap_err(),
+            error!(ErrorCode::InsufficientCurveLiquidity)
+        );
+    }
+
+    #[test]
+    fn quote_buy_stays_within_budget_and_matches_the_curve() {
+        let account = TokenAccount {
+            creation_slot: 100,
+            sniper_window_slots: 10,
+            sniper_tax_bps: 1000,
+            ..token()
+        };
+
+        let amount_in = 2_000_000_000;
+        let quote = account.quote_buy(amount_in, 200, account.platform_fee).unwrap();
+        assert!(quote.amount_in <= amount_in);
+
+        let amount = quote.amount_out + quote.treasury_amount;
+        let price = curve_buy_cost(account.virtual_sol_reserve, account.virtual_token_reserve, amount).unwrap();
+        assert_eq!(quote.creator_fee, bps_of(price, account.creator_fee).unwrap());
+        assert_eq!(quote.platform_fee, bps_of(price, account.platform_fee).unwrap());
+        assert_eq!(quote.treasury_amount, amount / 100);
+        assert!(quote.price_impact_bps > 0);
+        assert!(quote.post_trade_price >= account.curve_price().unwrap());
+
+        // Inside the launch window the sniper tax moves to the treasury
+        let window_quote = account.quote_buy(amount_in, 105, account.platform_fee).unwrap();
+        assert_eq!(window_quote.treasury_amount, amount / 100 + bps_of(amount, 1000).unwrap());
+    }
+
+    #[test]
+    fn quote_sell_mirrors_sell_tokens() {
+        let mut account = token();
+        let bought = 10_000_000 * account.token_unit();
+        let cost = curve_buy_cost(account.virtual_sol_reserve, account.virtual_token_reserve, bought).unwrap();
+        account.apply_curve_buy(bought, cost).unwrap();
+
+        let sold = 4_000_000 * account.token_unit();
+        let quote = account.quote_sell(sold, account.platform_fee).unwrap();
+        let sol_out = curve_sell_proceeds(account.virtual_sol_reserve, account.virtual_token_reserve, sold).unwrap();
+        assert_eq!(quote.amount_out + quote.creator_fee + quote.platform_fee, sol_out);
+        assert!(quote.price_impact_bps > 0);
+        assert!(quote.post_trade_price <= account.curve_price().unwrap());
+
+        assert_eq!(
+            account.quote_sell(bought + 1, account.platform_fee).unwrap_err(),
+            error!(ErrorCode::InsufficientCurveLiquidity)
+        );
+    }
+
+    #[test]
+    fn max_supply_rejects_mints_and_clips_quotes() {
+        let mut account = TokenAccount { decimals: 6, max_supply: 1_000_000_000, ..token() };
+
+        account.add_supply(600 * account.token_unit()).unwrap();
+        assert_eq!(account.remaining_supply(), 400 * account.token_unit());
+        assert_eq!(
+            account.add_supply(400 * account.token_unit() + 1).unwrap_err(),
+            error!(ErrorCode::MaxSupplyExceeded)
+        );
+
+        // A budget worth far more than the remaining allocation is clipped to it
+        let quote = account.quote_buy(50_000_000_000, 0, account.platform_fee).unwrap();
+        assert_eq!(quote.amount_out + quote.treasury_amount, account.remaining_supply());
+
+        // A curve reserve no larger than the max supply would run dry before it sells out
+        account.virtual_token_reserve = account.max_supply;
+        assert_eq!(
+            account.check_curve_covers_max_supply().unwrap_err(),
+            error!(ErrorCode::CurveReserveBelowMaxSupply)
+        );
+        account.virtual_token_reserve = account.max_supply + 1;
+        assert!(account.check_curve_covers_max_supply().is_ok());
+    }
+
+    #[test]
+    fn prices_are_per_whole_token_for_any_decimals() {
+        let mut account = token();
+        for decimals in [0u8, 6, MAX_TOKEN_DECIMALS] {
+            account.decimals = decimals;
+            account.virtual_token_reserve = default_virtual_token_reserve(decimals);
+            assert_eq!(account.curve_price().unwrap(), DEFAULT_VIRTUAL_SOL_RESERVE / DEFAULT_VIRTUAL_TOKEN_RESERVE);
+            assert_eq!(account.graduated_cost(account.token_unit()).unwrap(), GRADUATED_PRICE_PER_TOKEN);
+        }
+        assert!(validate_supply_params(MAX_TOKEN_DECIMALS + 1, 1).is_err());
+        assert!(validate_supply_params(6, 0).is_err());
+    }
+
+    #[test]
+    fn holding_limits_are_shares_of_a_fixed_supply() {
+        // Nothing has been minted yet, so a share of the current supply would reject any buy
+        let capped = TokenAccount { max_supply: 1_000_000, max_wallet_bps: 100, ..token() };
+        capped.check_holding_limits(10_000, 10_000).unwrap();
+        assert_eq!(
+            capped.check_holding_limits(1, 10_001).unwrap_err(),
+            error!(ErrorCode::MaxWalletExceeded)
+        );
+
+        let uncapped = TokenAccount {
+            limit_reference_supply: 1_000_000,
+            max_wallet_bps: 200,
+            max_tx_bps: 100,
+            ..token()
+        };
+
+        uncapped.check_holding_limits(10_000, 10_000).unwrap();
+        assert_eq!(
+            uncapped.check_holding_limits(10_001, 10_001).unwrap_err(),
+            error!(ErrorCode::MaxTransactionExceeded)
+        );
+        assert_eq!(
+            uncapped.check_holding_limits(10_000, 20_001).unwrap_err(),
+            error!(ErrorCode::MaxWalletExceeded)
+        );
+    }
+
+    #[test]
+    fn holders_of_an_initialized_token_can_burn() {
+        let mut account = TokenAccount { decimals: 6, max_supply: 1_000_000_000_000, ..token() };
+        let unit = account.token_unit();
+
+        // A curve buy credits the buyer and the treasury, as mint_tokens does
+        let mut buyer = holder();
+        let (treasury_amount, holder_amount) = split_mint_amount(1_000 * unit, 0).unwrap();
+        mint_to(&mut account, &mut holder(), treasury_amount);
+        mint_to(&mut account, &mut buyer, holder_amount);
+        buyer.curve_balance = holder_amount;
+        account.check_supply_invariant().unwrap();
+
+        // Burns lower the total and the max supply for good
+        account.burn_holder_tokens(&mut buyer, 400 * unit).unwrap();
+        assert_eq!(buyer.balance, holder_amount - 400 * unit);
+        assert_eq!(buyer.curve_balance, buyer.balance);
+        assert_eq!((account.total_supply, account.max_supply), (600 * unit, 999_600 * unit));
+        assert_eq!(account.remaining_supply(), 999_000 * unit);
+
+        let remaining = buyer.balance;
+        assert_eq!(
+            account.burn_holder_tokens(&mut buyer, remaining + 1).unwrap_err(),
+            error!(ErrorCode::InsufficientFunds)
+        );
+        assert_eq!(
+            account.burn_holder_tokens(&mut buyer, 0).unwrap_err(),
+            error!(ErrorCode::InvalidBurnAmount)
+        );
+        assert_eq!(
+            account.burn_supply(account.total_supply + 1).unwrap_err(),
+            error!(ErrorCode::CalculationError)
+        );
+
+        // Uncapped legacy tokens only lose supply
+        account.max_supply = 0;
+        account.burn_holder_tokens(&mut buyer, 100 * unit).unwrap();
+        assert_eq!((account.total_supply, account.max_supply), (500 * unit, 0));
+    }
+
+    #[test]
+    fn committed_buyback_fees_cannot_be_claimed() {
+        let mut vault = FeeVault {
+            token_account: Pubkey::new_unique(),
+            total_collected: 0,
+            total_claimed: 0,
+            buyback_reserved: 0,
+            total_buyback_spent: 0,
+            last_buyback_at: 0,
+            bump: 255,
+        };
+        assert_fits(&vault);
+
+        // 25% of a 1_000 lamport creator fee is committed to buybacks
+        vault.record_deposit(1_000).unwrap();
+        vault.commit_buyback(bps_of(1_000, 2500).unwrap()).unwrap();
+        assert_eq!(vault.claimable().unwrap(), 750);
+
+        vault.total_claimed += 750;
+        vault.spend_buyback(200).unwrap();
+        assert_eq!((vault.buyback_reserved, vault.claimable().unwrap()), (50, 0));
+        assert_eq!(vault.spend_buyback(51).unwrap_err(), error!(ErrorCode::CalculationError));
+    }
+
+    #[test]
+    fn buyback_burn_raises_price_without_changing_supply() {
+        let mut account = TokenAccount { max_supply: 500_000_000 * 10u64.pow(TOKEN_DECIMALS as u32), ..token() };
+        let (supply, max_supply, price) = (account.total_supply, account.max_supply, account.curve_price().unwrap());
+
+        let tokens = curve_tokens_for(account.virtual_sol_reserve, account.virtual_token_reserve, 5_000_000_000).unwrap();
+        let cost = curve_buy_cost(account.virtual_sol_reserve, account.virtual_token_reserve, tokens).unwrap();
+        assert!(cost <= 5_000_000_000);
+        account.apply_curve_buy(tokens, cost).unwrap();
+        account.add_supply(tokens).unwrap();
+        account.burn_supply(tokens).unwrap();
+
+        assert_eq!(account.total_supply, supply);
+        assert_eq!(account.max_supply, max_supply - tokens);
+        assert!(account.curve_price().unwrap() > price);
+    }
+
+    #[test]
+    fn referral_fee_is_carved_from_platform_fee() {
+        let mut referrer = Referrer {
+            wallet: Pubkey::new_unique(),
+            share_bps: 2000,
+            total_earned: 0,
+            total_claimed: 0,
+            is_active: true,
+            registered_at: 0,
+            bump: 255,
+        };
+        assert_fits(&referrer);
+
+        // 20% of a 1_001 lamport platform fee rounds down in the platform's favour
+        let referral_fee = bps_of(1_001, referrer.share_bps).unwrap();
+        assert_eq!((referral_fee, 1_001 - referral_fee), (200, 801));
+        assert_eq!(bps_of(1_001, 10000).unwrap(), 1_001);
+
+        referrer.record_earnings(referral_fee).unwrap();
+        referrer.record_earnings(referral_fee).unwrap();
+        referrer.total_claimed += 300;
+        assert_eq!(referrer.claimable().unwrap(), 100);
+    }
+
+    #[test]
+    fn trader_volume_rolls_off_after_thirty_days() {
+        let mut stats = TraderStats {
+            wallet: Pubkey::new_unique(),
+            daily_volume: [0; FEE_TIER_WINDOW_DAYS],
+            last_trade_day: 0,
+            total_volume: 0,
+            bump: 255,
+        };
+        assert_fits(&stats);
+
+        let day_one = 20_000 * SECONDS_PER_DAY;
+        stats.record_volume(day_one, 100).unwrap();
+        stats.record_volume(day_one + SECONDS_PER_DAY - 1, 50).unwrap();
+        stats.record_volume(day_one + 10 * SECONDS_PER_DAY, 25).unwrap();
+        assert_eq!(stats.rolling_volume(day_one + 29 * SECONDS_PER_DAY), 175);
+
+        // Day one leaves the window on day 31, the day 11 bucket on day 41
+        assert_eq!(stats.rolling_volume(day_one + 30 * SECONDS_PER_DAY), 25);
+        stats.record_volume(day_one + 35 * SECONDS_PER_DAY, 10).unwrap();
+        assert_eq!(stats.rolling_volume(day_one + 35 * SECONDS_PER_DAY), 35);
+        assert_eq!(stats.rolling_volume(day_one + 40 * SECONDS_PER_DAY), 10);
+        assert_eq!(stats.rolling_volume(day_one + 100 * SECONDS_PER_DAY), 0);
+        assert_eq!(stats.total_volume, 185);
+    }
+
+    #[test]
+    fn fee_tiers_discount_the_platform_fee_by_volume() {
+        let account = token();
+        let fee_tiers = vec![
+            FeeTier { min_volume: 10_000_000_000, discount_bps: 2000 },
+            FeeTier { min_volume: 100_000_000_000, discount_bps: 5000 },
+        ];
+        validate_fee_tiers(&fee_tiers).unwrap();
+        let config = PlatformConfig { authority: Pubkey::new_unique(), fee_tiers, bump: 255 };
+
+        let now = 20_000 * SECONDS_PER_DAY;
+        let mut stats = TraderStats {
+            wallet: Pubkey::new_unique(),
+            daily_volume: [0; FEE_TIER_WINDOW_DAYS],
+            last_trade_day: 0,
+            total_volume: 0,
+            bump: 255,
+        };
+        let base = (None, account.platform_fee);
+        assert_eq!(tiered_platform_fee(&account, Some(&config), Some(&stats), now).unwrap(), base);
+        assert_eq!(tiered_platform_fee(&account, None, Some(&stats), now).unwrap(), base);
+
+        stats.record_volume(now, 10_000_000_000).unwrap();
+        assert_eq!(tiered_platform_fee(&account, Some(&config), Some(&stats), now).unwrap(), (Some(0), 200));
+        stats.record_volume(now, 90_000_000_000).unwrap();
+        assert_eq!(tiered_platform_fee(&account, Some(&config), Some(&stats), now).unwrap(), (Some(1), 125));
+
+        let quote = account.quote_trade(1_000, 1_000_000, 125).unwrap();
+        assert_eq!(quote.platform_fee, bps_of(1_000_000_000, 125).unwrap());
+    }
+
+    #[test]
+    fn worst_case_fee_split_fits() {
+        let share = FeeShare { wallet: Pubkey::new_unique(), weight_bps: u64::MAX };
+        let recipient = FeeSplitRecipient {
+            wallet: Pubkey::new_unique(),
+            weight_bps: u64::MAX,
+            owed: u64::MAX,
+            total_claimed: u64::MAX,
+        };
+        assert_fits(&FeeSplit {
+            token_account: Pubkey::new_unique(),
+            recipients: vec![recipient; MAX_FEE_SPLIT_RECIPIENTS],
+            distributed: u64::MAX,
+            pending_shares: vec![share; MAX_FEE_SPLIT_RECIPIENTS],
+            pending_effective_at: i64::MAX,
+            bump: u8::MAX,
+        });
+    }
+
+    #[test]
+    fn fee_split_allocates_by_weight_across_updates() {
+        let (a, b, c) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let shares = vec![
+            FeeShare { wallet: a, weight_bps: 5000 },
+            FeeShare { wallet: b, weight_bps: 3000 },
+            FeeShare { wallet: c, weight_bps: 2000 },
+        ];
+        validate_fee_split(&shares).unwrap();
+
+        let mut split = FeeSplit {
+            token_account: Pubkey::new_unique(),
+            recipients: Vec::new(),
+            distributed: 1_000,
+            pending_shares: Vec::new(),
+            pending_effective_at: 0,
+            bump: 255,
+        };
+        split.apply_shares(&shares).unwrap();
+
+        // Only fees released after the split started are shared out
+        split.allocate(11_000).unwrap();
+        assert_eq!(split.claim(a).unwrap(), 5_000);
+        assert_eq!(split.claim(a).unwrap_err(), error!(ErrorCode::NoFeesToClaim));
+
+        // c is removed before claiming and keeps what it is owed, but earns nothing further
+        split.allocate(11_000).unwrap();
+        split.apply_shares(&[FeeShare { wallet: a, weight_bps: 5000 }, FeeShare { wallet: b, weight_bps: 5000 }]).unwrap();
+        split.allocate(21_000).unwrap();
+        assert_eq!(split.claim(b).unwrap(), 3_000 + 5_000);
+        assert_eq!(split.claim(c).unwrap(), 2_000);
+        assert_eq!(split.claim(c).unwrap_err(), error!(ErrorCode::Unauthorized));
+        assert_eq!(split.claim(a).unwrap(), 5_000);
+        assert_eq!(split.distributed, 21_000);
+
+        let uneven = vec![FeeShare { wallet: a, weight_bps: 5000 }, FeeShare { wallet: b, weight_bps: 4999 }];
+        assert_eq!(validate_fee_split(&uneven).unwrap_err(), error!(ErrorCode::InvalidFeeSplit));
+        let duplicate = vec![FeeShare { wallet: a, weight_bps: 5000 }, FeeShare { wallet: a, weight_bps: 5000 }];
+        assert_eq!(validate_fee_split(&duplicate).unwrap_err(), error!(ErrorCode::InvalidFeeSplit));
+    }
 }